@@ -6,6 +6,13 @@ const GREY = '#88888855';
 const PCT = { type: 'custom', minMove: 0.0001, formatter: v => (v >= 0 ? '+' : '') + ((Math.exp(v) - 1) * 100).toFixed(1) + '%' };
 
 let seriesByPair = new Map(), bulk, bulkHost;
+// overlay id → its series (two for a band); and what the legend has toggled, kept across redraws
+let overlaySeries = new Map(), shown = new Map();
+let breadthSeries;
+// query of the chart on screen, and whether the click → drill-down hook is on this chart yet
+let query = '', clickWired = false;
+const BREADTH_PANE = 1;
+const BREADTH = { above: '#e2e8f0', ad: '#fbbf24', highs: '#22c55e88', lows: '#ef444488' };
 
 // The grey background pairs, drawn as one canvas pass pinned to the chart's scales.
 // Non-interactive (no crosshair / legend), but zooms & pans with the real series.
@@ -82,11 +89,93 @@ export function draw(chart, d, viewSpec, lib) {
   const host = d.series[0] && seriesByPair.get(d.series[0].pair);
   if (host && host !== bulkHost) { host.attachPrimitive(bulk); bulkHost = host; }
 
+  drawOverlays(chart, d.overlays || [], lib);
+  drawBreadth(chart, d.breadth, lib);
+
+  query = d.query || '';
+  if (!clickWired) {
+    chart.subscribeClick(p => {
+      if (!p.hoveredSeries) return;
+      for (const [pair, s] of seriesByPair) if (s === p.hoveredSeries) return pick(pair);
+    });
+    clickWired = true;
+  }
+
   chart.timeScale().fitContent();
-  renderLegend(el, d.legend, d.title);
+  renderLegend(el, d.legend, d.title, d.overlays || [], d.breadth);
+}
+
+// Sub-pane under the chart: % of pairs above their start (right axis, 0–100), the advance/decline line
+// and new highs (up) / lows (down) as histograms, the last two on unlabelled overlay scales.
+function drawBreadth(chart, b, lib) {
+  if (!b) {
+    if (breadthSeries) { Object.values(breadthSeries).forEach(s => chart.removeSeries(s)); breadthSeries = null; }
+    return;
+  }
+  if (!breadthSeries) {
+    const common = { priceLineVisible: false, crosshairMarkerVisible: false };
+    breadthSeries = {
+      above: chart.addSeries(lib.LineSeries, { ...common, color: BREADTH.above, lineWidth: 1, priceFormat: { type: 'custom', minMove: 1, formatter: v => v.toFixed(0) + '%' } }, BREADTH_PANE),
+      ad: chart.addSeries(lib.LineSeries, { ...common, color: BREADTH.ad, lineWidth: 1, priceScaleId: 'ad', lastValueVisible: false }, BREADTH_PANE),
+      highs: chart.addSeries(lib.HistogramSeries, { ...common, color: BREADTH.highs, priceScaleId: 'hl', lastValueVisible: false }, BREADTH_PANE),
+      lows: chart.addSeries(lib.HistogramSeries, { ...common, color: BREADTH.lows, priceScaleId: 'hl', lastValueVisible: false }, BREADTH_PANE),
+    };
+    breadthSeries.ad.priceScale().applyOptions({ scaleMargins: { top: 0.1, bottom: 0.1 } });
+    breadthSeries.highs.priceScale().applyOptions({ scaleMargins: { top: 0.55, bottom: 0 } });
+    const pane = chart.panes()[BREADTH_PANE];
+    if (pane) pane.setHeight(Math.round(chart.chartElement().clientHeight * 0.25));
+  }
+  const pts = (ln, f = v => v) => ln.time.map((t, j) => ({ time: t, value: f(ln.value[j]) }));
+  breadthSeries.above.setData(pts(b.above_start));
+  breadthSeries.ad.setData(pts(b.ad_line));
+  breadthSeries.highs.setData(pts(b.new_highs));
+  breadthSeries.lows.setData(pts(b.new_lows, v => -v));
 }
 
-function renderLegend(el, legend, title) {
+// Whole-market series over the pairs. Each keeps its series across redraws (like per-pair ones);
+// visibility is the legend's call once the user has toggled it, the payload's default before that.
+function drawOverlays(chart, overlays, lib) {
+  const seen = new Set();
+  for (const o of overlays) {
+    seen.add(o.id);
+    if (!shown.has(o.id)) shown.set(o.id, o.visible);
+    const edges = o.lower ? [o.line, o.lower] : [o.line];
+    let ss = overlaySeries.get(o.id);
+    if (!ss || ss.length !== edges.length) {
+      if (ss) ss.forEach(s => chart.removeSeries(s));
+      ss = edges.map(() => chart.addSeries(lib.LineSeries, {
+        color: o.color, lineWidth: o.lower ? 1 : 2, lineStyle: o.lower ? lib.LineStyle.Dashed : lib.LineStyle.Solid,
+        priceScaleId: o.scale || 'right',
+        priceFormat: o.scale ? { type: 'price', precision: 2, minMove: 0.01 } : PCT,
+        priceLineVisible: false, lastValueVisible: !o.lower, crosshairMarkerVisible: false,
+      }));
+      overlaySeries.set(o.id, ss);
+      // a separate scale (correlation, -1…1) gets a strip along the bottom instead of fighting the returns axis
+      if (o.scale) chart.priceScale(o.scale).applyOptions({ scaleMargins: { top: 0.78, bottom: 0.02 }, borderVisible: false });
+    }
+    ss.forEach((s, k) => {
+      s.applyOptions({ color: o.color, visible: shown.get(o.id) });
+      s.setData(edges[k].time.map((t, j) => ({ time: t, value: edges[k].value[j] })));
+    });
+  }
+  for (const [id, ss] of overlaySeries) {
+    if (!seen.has(id)) { ss.forEach(s => chart.removeSeries(s)); overlaySeries.delete(id); }
+  }
+}
+
+// The Pair panel listens for this; the detail is JSON so the Rust side can decode it as a plain string.
+function pick(pair) {
+  window.dispatchEvent(new CustomEvent('ms:pair', { detail: JSON.stringify({ pair, query }) }));
+}
+
+function toggleOverlay(id, row) {
+  const on = !shown.get(id);
+  shown.set(id, on);
+  (overlaySeries.get(id) || []).forEach(s => s.applyOptions({ visible: on }));
+  row.style.opacity = on ? '1' : '0.4';
+}
+
+function renderLegend(el, legend, title, overlays, breadth) {
   let box = el.querySelector('.ms-legend');
   if (!box) {
     box = document.createElement('div');
@@ -106,6 +195,35 @@ function renderLegend(el, legend, title) {
     row.textContent = e.label;
     row.style.color = e.color;
     row.style.whiteSpace = 'pre';
+    if (e.pair) {
+      row.style.pointerEvents = 'auto';
+      row.style.cursor = 'pointer';
+      row.title = `open ${e.pair}`;
+      row.addEventListener('click', () => pick(e.pair));
+    }
+    box.appendChild(row);
+  }
+  // the box ignores the pointer so the chart stays pannable underneath; only these rows take clicks
+  if (overlays.length) {
+    const sep = document.createElement('div');
+    sep.style.cssText = 'height:4px';
+    box.appendChild(sep);
+  }
+  for (const o of overlays) {
+    const row = document.createElement('div');
+    row.textContent = (o.lower ? '┆ ' : '━ ') + o.label;
+    row.title = 'click to toggle';
+    row.style.cssText = `color:${o.color};white-space:pre;pointer-events:auto;cursor:pointer;user-select:none`;
+    row.style.opacity = shown.get(o.id) ? '1' : '0.4';
+    row.addEventListener('click', () => toggleOverlay(o.id, row));
+    box.appendChild(row);
+  }
+  if (breadth) {
+    const row = document.createElement('div');
+    row.style.cssText = 'white-space:pre;margin-top:4px;color:#94a3b8';
+    const key = (text, color) => { const s = document.createElement('span'); s.textContent = text; s.style.color = color; return s; };
+    row.append('breadth ↓ ', key('% above start', BREADTH.above), ' · ', key('A/D', BREADTH.ad), ' · ',
+      key('new highs', BREADTH.highs), '/', key('lows', BREADTH.lows), ` (${breadth.window} bars)`);
     box.appendChild(row);
   }
 }
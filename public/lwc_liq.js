@@ -0,0 +1,94 @@
+// "What we chart" for the Liquidations panel: the price path, with liquidations binned into price × time cells
+// painted behind it. Same contract as lwc_draw.js — v_utils::lwc owns the chart instance and calls draw(chart, data, viewSpec, lib).
+
+const LONG = '239,68,68', SHORT = '34,197,94';
+
+let price, cells;
+
+// The heatmap cells, drawn as one canvas pass pinned to the price series' scales, like lwc_draw.js's BulkLines.
+class HeatCells {
+  // d: { bucket_secs, price_step, cells: [{ time, price, long_usd, short_usd }] }
+  setData(d) {
+    this._d = d;
+    let lo = Infinity, hi = -Infinity;
+    for (const c of d.cells) { if (c.price < lo) lo = c.price; if (c.price + d.price_step > hi) hi = c.price + d.price_step; }
+    this._range = d.cells.length ? { minValue: lo, maxValue: hi } : null;
+    // intensity is relative to the heaviest cell, on a sqrt scale so one cascade doesn't wash out the rest
+    this._max = d.cells.reduce((m, c) => Math.max(m, c.long_usd + c.short_usd), 0);
+    this._req && this._req();
+  }
+  attached({ chart, series, requestUpdate }) { this._chart = chart; this._series = series; this._req = requestUpdate; }
+  updateAllViews() {}
+  autoscaleInfo() { return this._range ? { priceRange: this._range } : null; }
+  paneViews() { return [{ zOrder: () => 'bottom', renderer: () => ({ draw: t => this._draw(t) }) }]; }
+  _draw(target) {
+    const d = this._d;
+    if (!d || !d.cells.length || !this._max) return;
+    const ts = this._chart.timeScale();
+    const spacing = ts.options().barSpacing;
+    target.useBitmapCoordinateSpace(scope => {
+      const ctx = scope.context, hr = scope.horizontalPixelRatio, vr = scope.verticalPixelRatio;
+      for (const c of d.cells) {
+        const x0 = ts.timeToCoordinate(c.time);
+        if (x0 == null) continue;
+        const x1 = ts.timeToCoordinate(c.time + d.bucket_secs) ?? x0 + spacing;
+        const y0 = this._series.priceToCoordinate(c.price + d.price_step), y1 = this._series.priceToCoordinate(c.price);
+        if (y0 == null || y1 == null) continue;
+        const total = c.long_usd + c.short_usd;
+        const alpha = 0.1 + 0.8 * Math.sqrt(total / this._max);
+        ctx.fillStyle = `rgba(${c.long_usd >= c.short_usd ? LONG : SHORT},${alpha.toFixed(3)})`;
+        // bars are centred on their time; a cell spans its whole bucket
+        ctx.fillRect((x0 - spacing / 2) * hr, y0 * vr, Math.max(1, (x1 - x0)) * hr, Math.max(1, y1 - y0) * vr);
+      }
+    });
+  }
+}
+
+export function draw(chart, d, viewSpec, lib) {
+  const el = chart.chartElement().parentElement;
+  chart.applyOptions({
+    layout: { background: { color: 'transparent' }, textColor: '#cbd5e1' },
+    grid: { vertLines: { color: '#ffffff10' }, horzLines: { color: '#ffffff10' } },
+    rightPriceScale: { borderVisible: false },
+    timeScale: { timeVisible: true, borderVisible: false },
+  });
+
+  if (!price) {
+    price = chart.addSeries(lib.LineSeries, { color: '#e2e8f0', lineWidth: 1, priceLineVisible: false, lastValueVisible: true });
+    cells = new HeatCells();
+    price.attachPrimitive(cells);
+  }
+  price.setData(d.price.time.map((t, i) => ({ time: t, value: d.price.value[i] })));
+  cells.setData(d);
+
+  chart.timeScale().fitContent();
+  renderTitle(el, d);
+}
+
+function renderTitle(el, d) {
+  let box = el.querySelector('.liq-title');
+  if (!box) {
+    box = document.createElement('div');
+    box.className = 'liq-title';
+    box.style.cssText = 'position:absolute;top:8px;left:8px;z-index:3;font:11px ui-monospace,monospace;line-height:1.4;pointer-events:none;color:#cbd5e1';
+    el.appendChild(box);
+  }
+  box.replaceChildren();
+  const t = document.createElement('div');
+  t.textContent = d.title + ' liquidations';
+  t.style.fontWeight = '600';
+  box.appendChild(t);
+
+  const sum = k => d.cells.reduce((s, c) => s + c[k], 0);
+  const usd = v => v >= 1e9 ? (v / 1e9).toFixed(2) + 'B' : v >= 1e6 ? (v / 1e6).toFixed(1) + 'M' : (v / 1e3).toFixed(0) + 'K';
+  const key = document.createElement('div');
+  key.style.color = '#94a3b8';
+  key.innerHTML = `<span style="color:rgb(${LONG})">■</span> longs ${usd(sum('long_usd'))} · <span style="color:rgb(${SHORT})">■</span> shorts ${usd(sum('short_usd'))}`;
+  box.appendChild(key);
+  if (d.since != null) {
+    const since = document.createElement('div');
+    since.style.color = '#64748b';
+    since.textContent = 'Binance + Bybit, collected since ' + new Date(d.since * 1000).toISOString().slice(0, 16).replace('T', ' ') + ' UTC';
+    box.appendChild(since);
+  }
+}
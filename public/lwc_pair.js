@@ -0,0 +1,64 @@
+// "What we chart" for the Pair drill-down panel: one pair's candles, quote volume and long-account share.
+// Same contract as lwc_draw.js — v_utils::lwc owns the chart instance and calls draw(chart, data, viewSpec, lib).
+
+const UP = '#22c55e', DOWN = '#ef4444';
+const LSR_PANE = 1;
+
+let candles, volume, lsr;
+
+export function draw(chart, d, viewSpec, lib) {
+  const el = chart.chartElement().parentElement;
+  chart.applyOptions({
+    layout: { background: { color: 'transparent' }, textColor: '#cbd5e1' },
+    grid: { vertLines: { color: '#ffffff10' }, horzLines: { color: '#ffffff10' } },
+    rightPriceScale: { borderVisible: false },
+    timeScale: { timeVisible: true, borderVisible: false },
+  });
+
+  if (!candles) {
+    candles = chart.addSeries(lib.CandlestickSeries, { upColor: UP, downColor: DOWN, wickUpColor: UP, wickDownColor: DOWN, borderVisible: false, priceLineVisible: false });
+    // volume on its own unlabelled scale, squeezed under the candles
+    volume = chart.addSeries(lib.HistogramSeries, { priceScaleId: 'vol', priceFormat: { type: 'volume' }, lastValueVisible: false, priceLineVisible: false });
+    volume.priceScale().applyOptions({ scaleMargins: { top: 0.8, bottom: 0 } });
+    candles.priceScale().applyOptions({ scaleMargins: { top: 0.05, bottom: 0.25 } });
+  }
+  candles.setData(d.time.map((t, i) => ({ time: t, open: d.open[i], high: d.high[i], low: d.low[i], close: d.close[i] })));
+  volume.setData(d.time.map((t, i) => ({ time: t, value: d.volume[i], color: d.close[i] >= d.open[i] ? UP + '55' : DOWN + '55' })));
+
+  if (d.lsr) {
+    if (!lsr) {
+      lsr = chart.addSeries(lib.LineSeries, {
+        color: '#fbbf24', lineWidth: 1, priceLineVisible: false,
+        priceFormat: { type: 'custom', minMove: 0.001, formatter: v => (v * 100).toFixed(1) + '% long' },
+      }, LSR_PANE);
+      const pane = chart.panes()[LSR_PANE];
+      if (pane) pane.setHeight(Math.round(chart.chartElement().clientHeight * 0.25));
+    }
+    lsr.setData(d.lsr.time.map((t, i) => ({ time: t, value: d.lsr.value[i] })));
+  } else if (lsr) {
+    chart.removeSeries(lsr);
+    lsr = null;
+  }
+
+  chart.timeScale().fitContent();
+  renderTitle(el, d.title, !!d.lsr);
+}
+
+function renderTitle(el, title, hasLsr) {
+  let box = el.querySelector('.pair-title');
+  if (!box) {
+    box = document.createElement('div');
+    box.className = 'pair-title';
+    box.style.cssText = 'position:absolute;top:8px;left:8px;z-index:3;font:11px ui-monospace,monospace;line-height:1.4;pointer-events:none;color:#cbd5e1';
+    el.appendChild(box);
+  }
+  box.replaceChildren();
+  const t = document.createElement('div');
+  t.textContent = title;
+  t.style.fontWeight = '600';
+  box.appendChild(t);
+  const key = document.createElement('div');
+  key.style.color = '#94a3b8';
+  key.textContent = hasLsr ? 'candles · volume · ↓ Binance global accounts long' : 'candles · volume';
+  box.appendChild(key);
+}
@@ -25,7 +25,7 @@ pub mod server_impl {
 		config::{LiveSettings, Settings},
 	};
 
-	fn get_settings() -> Result<Settings, ServerFnError> {
+	pub fn get_settings() -> Result<Settings, ServerFnError> {
 		use_context::<LiveSettings>()
 			.ok_or_else(|| ServerFnError::new("Settings not available"))?
 			.config()
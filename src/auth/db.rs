@@ -88,6 +88,35 @@ impl Database {
 		.await
 		.wrap_err("failed to create admin_files table")?;
 
+		sqlx::query(
+			"CREATE TABLE IF NOT EXISTS lsr_watchlists (
+                id TEXT PRIMARY KEY,
+                user_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                pairs TEXT NOT NULL,
+                share_code TEXT NOT NULL UNIQUE,
+                updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+                UNIQUE (user_id, name)
+            )",
+		)
+		.execute(&pool)
+		.await
+		.wrap_err("failed to create lsr_watchlists table")?;
+
+		sqlx::query(
+			"CREATE TABLE IF NOT EXISTS lsr_alert_rules (
+                id TEXT PRIMARY KEY,
+                user_id TEXT NOT NULL,
+                pair TEXT NOT NULL DEFAULT '',
+                condition TEXT NOT NULL,
+                delivery TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+            )",
+		)
+		.execute(&pool)
+		.await
+		.wrap_err("failed to create lsr_alert_rules table")?;
+
 		Ok(Self { pool })
 	}
 
@@ -414,6 +443,108 @@ impl Database {
 		}))
 	}
 
+	pub async fn list_lsr_watchlists(&self, user_id: &str) -> Result<Vec<LsrWatchlistRow>> {
+		let rows = sqlx::query("SELECT id, name, pairs, share_code FROM lsr_watchlists WHERE user_id = ? ORDER BY name")
+			.bind(user_id)
+			.fetch_all(&self.pool)
+			.await
+			.wrap_err("failed to list LSR watchlists")?;
+
+		Ok(rows.into_iter().map(watchlist_from_row).collect())
+	}
+
+	/// Creates the list, or replaces the pairs of the user's list by that name. Returns it as stored — an
+	/// existing list keeps its id and share code.
+	pub async fn save_lsr_watchlist(&self, id: &str, user_id: &str, name: &str, pairs: &str, share_code: &str) -> Result<LsrWatchlistRow> {
+		let row = sqlx::query(
+			"INSERT INTO lsr_watchlists (id, user_id, name, pairs, share_code) VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT (user_id, name) DO UPDATE SET pairs = excluded.pairs, updated_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now')
+            RETURNING id, name, pairs, share_code",
+		)
+		.bind(id)
+		.bind(user_id)
+		.bind(name)
+		.bind(pairs)
+		.bind(share_code)
+		.fetch_one(&self.pool)
+		.await
+		.wrap_err("failed to save LSR watchlist")?;
+
+		Ok(watchlist_from_row(row))
+	}
+
+	pub async fn get_lsr_watchlist_by_share_code(&self, share_code: &str) -> Result<Option<LsrWatchlistRow>> {
+		let row = sqlx::query("SELECT id, name, pairs, share_code FROM lsr_watchlists WHERE share_code = ? LIMIT 1")
+			.bind(share_code)
+			.fetch_optional(&self.pool)
+			.await
+			.wrap_err("failed to get LSR watchlist")?;
+
+		Ok(row.map(watchlist_from_row))
+	}
+
+	/// Only ever deletes the user's own list.
+	pub async fn delete_lsr_watchlist(&self, user_id: &str, id: &str) -> Result<()> {
+		sqlx::query("DELETE FROM lsr_watchlists WHERE id = ? AND user_id = ?")
+			.bind(id)
+			.bind(user_id)
+			.execute(&self.pool)
+			.await
+			.wrap_err("failed to delete LSR watchlist")?;
+		Ok(())
+	}
+
+	pub async fn create_lsr_alert_rule(&self, id: &str, user_id: &str, pair: &str, condition: &str, delivery: &str) -> Result<()> {
+		sqlx::query("INSERT INTO lsr_alert_rules (id, user_id, pair, condition, delivery) VALUES (?, ?, ?, ?, ?)")
+			.bind(id)
+			.bind(user_id)
+			.bind(pair)
+			.bind(condition)
+			.bind(delivery)
+			.execute(&self.pool)
+			.await
+			.wrap_err("failed to create LSR alert rule")?;
+		Ok(())
+	}
+
+	/// One user's rules, or everyone's when `user_id` is `None`.
+	pub async fn list_lsr_alert_rules(&self, user_id: Option<&str>) -> Result<Vec<LsrAlertRuleRow>> {
+		let rows = sqlx::query(
+			"SELECT r.id, r.user_id, u.email, u.email_verified, r.pair, r.condition, r.delivery FROM lsr_alert_rules r
+            JOIN users u ON u.id = r.user_id
+            WHERE ?1 IS NULL OR r.user_id = ?1
+            ORDER BY r.created_at",
+		)
+		.bind(user_id)
+		.fetch_all(&self.pool)
+		.await
+		.wrap_err("failed to list LSR alert rules")?;
+
+		Ok(rows
+			.into_iter()
+			.map(|r| LsrAlertRuleRow {
+				id: r.get("id"),
+				user_id: r.get("user_id"),
+				user_email: r.get("email"),
+				user_email_verified: r.get::<i64, _>("email_verified") != 0,
+				pair: r.get("pair"),
+				condition: r.get("condition"),
+				delivery: r.get("delivery"),
+			})
+			.collect())
+	}
+
+	/// Only ever deletes the user's own rule.
+	pub async fn delete_lsr_alert_rule(&self, user_id: &str, id: &str) -> Result<()> {
+		sqlx::query("DELETE FROM lsr_alert_rules WHERE id = ? AND user_id = ?")
+			.bind(id)
+			.bind(user_id)
+			.execute(&self.pool)
+			.await
+			.wrap_err("failed to delete LSR alert rule")?;
+		Ok(())
+	}
+
 	pub async fn delete_admin_file(&self, id: &str) -> Result<()> {
 		sqlx::query("DELETE FROM admin_files WHERE id = ?")
 			.bind(id)
@@ -424,6 +555,26 @@ impl Database {
 	}
 }
 
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct LsrWatchlistRow {
+	pub id: String,
+	pub name: String,
+	/// Comma-separated base symbols, as in `?lsr=`.
+	pub pairs: String,
+	pub share_code: String,
+}
+/// `condition` and `delivery` are JSON, owned by the LSR alerts module.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct LsrAlertRuleRow {
+	pub id: String,
+	pub user_id: String,
+	pub user_email: String,
+	pub user_email_verified: bool,
+	/// Base symbol; empty for any pair.
+	pub pair: String,
+	pub condition: String,
+	pub delivery: String,
+}
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 pub struct AdminFile {
 	pub id: String,
@@ -441,6 +592,15 @@ pub struct AdminFileWithData {
 	pub uploaded_by: String,
 	pub uploaded_at: String,
 }
+fn watchlist_from_row(r: sqlx::sqlite::SqliteRow) -> LsrWatchlistRow {
+	LsrWatchlistRow {
+		id: r.get("id"),
+		name: r.get("name"),
+		pairs: r.get("pairs"),
+		share_code: r.get("share_code"),
+	}
+}
+
 fn none_if_empty(s: String) -> Option<String> {
 	if s.is_empty() { None } else { Some(s) }
 }
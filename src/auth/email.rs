@@ -27,6 +27,23 @@ impl EmailSender {
 		Ok(Self { mailer, from })
 	}
 
+	/// Plain-text mail to one recipient.
+	pub async fn send(&self, to_email: &str, subject: &str, body: String) -> Result<()> {
+		let to: Mailbox = to_email.parse().context("Invalid recipient email")?;
+
+		let email = Message::builder()
+			.from(self.from.clone())
+			.to(to)
+			.subject(subject)
+			.header(ContentType::TEXT_PLAIN)
+			.body(body)
+			.context("Failed to build email")?;
+
+		self.mailer.send(email).await.context("Failed to send email")?;
+
+		Ok(())
+	}
+
 	pub async fn send_verification_email(&self, to_email: &str, username: &str, verification_link: &str) -> Result<()> {
 		let to: Mailbox = to_email.parse().context("Invalid recipient email")?;
 
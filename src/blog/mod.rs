@@ -455,14 +455,12 @@ fn score_post(post: &PostSummary, query: &str) -> f64 {
 	for qt in &query_tokens {
 		let mut term_score = 0.0;
 
-		// Title matches (10x weight)
+		// Title matches (10x weight). Fuzzy, but only alignments earning at least a point per query char:
+		// a query scattered thinly across a word isn't a match
+		let min_score = qt.chars().count() as f64;
 		for tt in &title_tokens {
-			if tt == qt {
-				term_score += 100.0;
-			} else if tt.starts_with(qt) {
-				term_score += 50.0;
-			} else if tt.contains(qt) {
-				term_score += 20.0;
+			if let Some(m) = crate::fuzzy::score(qt, tt).filter(|m| m.score >= min_score) {
+				term_score += 5.0 * m.score;
 			}
 		}
 
@@ -6,9 +6,14 @@ use std::collections::HashMap;
 #[derive(Clone, Debug, v_utils::macros::MyConfigPrimitives)]
 #[cfg_attr(feature = "ssr", derive(v_utils::macros::Settings, v_utils::macros::LiveSettings))]
 pub struct Settings {
+	/// Serve dashboard sources offline: persisted copies regardless of age, fixtures on a cold cache.
 	pub mock: Option<bool>,
 	/// Multiple of a source's refresh interval past which its cached copy is surfaced as stale.
 	pub stale_multiplier: Option<f64>,
+	/// Days of per-poll history a dashboard source keeps; frequent or bulky sources keep less.
+	pub history_retention_days: Option<u32>,
+	/// Sources the background scheduler may refresh at once; bounds the combined upstream fan-out.
+	pub refresh_concurrency: Option<usize>,
 	#[serde(default)]
 	pub smtp: SmtpConfig,
 	#[serde(default)]
@@ -21,6 +26,14 @@ pub struct Settings {
 	#[serde(default)]
 	#[primitives(skip)]
 	pub admin: AdminConf,
+	/// Contracts the CFTC panel offers
+	#[serde(default)]
+	#[primitives(skip)]
+	pub cftc: CftcConf,
+	/// Pairs and wall threshold of the order book depth panel
+	#[serde(default)]
+	#[primitives(skip)]
+	pub depth: DepthConf,
 }
 impl Settings {
 	pub fn mock(&self) -> bool {
@@ -30,6 +43,14 @@ impl Settings {
 	pub fn stale_multiplier(&self) -> f64 {
 		self.stale_multiplier.unwrap_or(1.15)
 	}
+
+	pub fn history_retention_days(&self) -> u32 {
+		self.history_retention_days.unwrap_or(30)
+	}
+
+	pub fn refresh_concurrency(&self) -> usize {
+		self.refresh_concurrency.unwrap_or(2).max(1)
+	}
 }
 
 impl Default for Settings {
@@ -37,10 +58,14 @@ impl Default for Settings {
 		Self {
 			mock: Some(false),
 			stale_multiplier: Some(1.15),
+			history_retention_days: Some(30),
+			refresh_concurrency: Some(2),
 			smtp: SmtpConfig::default(),
 			google_oauth: GoogleOAuthConfig::default(),
 			site_url: __default_site_url(),
 			admin: AdminConf::default(),
+			cftc: CftcConf::default(),
+			depth: DepthConf::default(),
 		}
 	}
 }
@@ -56,6 +81,46 @@ pub struct AdminConf {
 	#[serde(default)]
 	pub creds: Option<HashMap<String, HashMap<String, String>>>,
 }
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct CftcConf {
+	/// In switcher order; the first is what the panel opens on. Each is fetched and cached on its own.
+	/// Format: [{ code = "133741", name = "BTC" }, { code = "088691", name = "Gold", physical = true }, …]
+	#[serde(default = "__default_cftc_contracts")]
+	pub contracts: Vec<CftcContract>,
+}
+impl Default for CftcConf {
+	fn default() -> Self {
+		Self {
+			contracts: __default_cftc_contracts(),
+		}
+	}
+}
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct CftcContract {
+	/// CFTC contract market code, as in the COT reports' `cftc_contract_market_code`.
+	pub code: String,
+	pub name: String,
+	/// A physically delivered commodity, which the CFTC also publishes the Disaggregated report for.
+	#[serde(default)]
+	pub physical: bool,
+}
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct DepthConf {
+	/// Binance USDT perps, in switcher order; each is snapshotted on its own. Format: ["BTC-USDT", …]
+	#[serde(default = "__default_depth_pairs")]
+	pub pairs: Vec<String>,
+	/// A level is a wall once it holds this many times the average level's size within the panel's band.
+	#[serde(default = "__default_wall_multiple")]
+	pub wall_multiple: f64,
+}
+impl Default for DepthConf {
+	fn default() -> Self {
+		Self {
+			pairs: __default_depth_pairs(),
+			wall_multiple: __default_wall_multiple(),
+		}
+	}
+}
 #[derive(Clone, Debug, v_utils::macros::MyConfigPrimitives, v_utils::macros::SettingsNested)]
 pub struct SmtpConfig {
 	pub host: String,
@@ -82,6 +147,34 @@ fn __default_site_url() -> String {
 	"http://localhost:61156".to_string()
 }
 
+fn __default_cftc_contracts() -> Vec<CftcContract> {
+	[
+		("133741", "BTC (CME)"),
+		("133742", "Micro BTC (CME)"),
+		("146021", "ETH (CME)"),
+		("146022", "Micro ETH (CME)"),
+		("177741", "SOL (CME)"),
+		("177742", "Micro SOL (CME)"),
+		("13874A", "E-mini S&P 500"),
+		("209742", "Nasdaq-100 E-mini"),
+	]
+	.into_iter()
+	.map(|(code, name)| CftcContract {
+		code: code.to_owned(),
+		name: name.to_owned(),
+		physical: false,
+	})
+	.collect()
+}
+
+fn __default_depth_pairs() -> Vec<String> {
+	["BTC-USDT", "ETH-USDT", "SOL-USDT"].map(str::to_owned).to_vec()
+}
+
+fn __default_wall_multiple() -> f64 {
+	8.0
+}
+
 // MyConfigPrimitives enables `{ env = "VAR" }` for plain String fields
 
 impl Default for SmtpConfig {
@@ -0,0 +1,102 @@
+//! Record/replay of upstream responses. Each upstream call in a fetcher goes through [`http`] (raw
+//! response bodies) or [`tape`] (typed results of `v_exchanges` calls) under a stable key. With
+//! `DASHBOARDS_CASSETTE=record` the call runs live and its response is saved to
+//! `$DASHBOARDS_CASSETTE_DIR/<key>.json` (default `cassettes/`); with `=replay` that file stands in
+//! for upstream, which is never touched — so parsing and normalization run on real payloads offline.
+//! Env rather than `Settings`: replay has to work where no config (or reactive context) exists.
+//! Tests replay from the checked-in [`FIXTURES`] instead, whatever the env says (see [`replay`]).
+use std::{
+	future::Future,
+	path::{Path, PathBuf},
+	sync::LazyLock,
+};
+
+use color_eyre::eyre::{Result, WrapErr as _, eyre};
+use serde::{Serialize, de::DeserializeOwned};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Mode {
+	Live,
+	Record,
+	Replay,
+}
+static MODE: LazyLock<Mode> = LazyLock::new(|| match std::env::var("DASHBOARDS_CASSETTE").as_deref() {
+	Ok("record") => Mode::Record,
+	Ok("replay") => Mode::Replay,
+	Ok("") | Err(_) => Mode::Live,
+	Ok(other) => {
+		tracing::warn!("unknown DASHBOARDS_CASSETTE={other} (expected `record` or `replay`); hitting upstream live");
+		Mode::Live
+	}
+});
+static DIR: LazyLock<PathBuf> = LazyLock::new(|| std::env::var_os("DASHBOARDS_CASSETTE_DIR").map_or_else(|| PathBuf::from("cassettes"), PathBuf::from));
+
+/// Recordings the tests replay, re-recorded with `DASHBOARDS_CASSETTE=record DASHBOARDS_CASSETTE_DIR=fixtures/cassettes`.
+#[cfg(test)]
+pub const FIXTURES: &str = "fixtures/cassettes";
+#[cfg(test)]
+tokio::task_local! {
+	static REPLAYING: ();
+}
+
+/// Runs `f` replaying from [`FIXTURES`]. Task-scoped rather than through the env, so tests that do hit a
+/// (local) upstream can run alongside in the same process.
+#[cfg(test)]
+pub async fn replay<F: Future>(f: F) -> F::Output {
+	REPLAYING.scope((), f).await
+}
+
+fn mode() -> Mode {
+	#[cfg(test)]
+	if REPLAYING.try_with(|_| ()).is_ok() {
+		return Mode::Replay;
+	}
+	*MODE
+}
+
+/// Raw response body of `live`, recorded verbatim.
+pub async fn http(key: &str, live: impl Future<Output = Result<String>>) -> Result<String> {
+	let p = path(key);
+	match mode() {
+		Mode::Live => live.await,
+		Mode::Replay => std::fs::read_to_string(&p).wrap_err_with(|| format!("no recording for `{key}` at {}", p.display())),
+		Mode::Record => {
+			let body = live.await?;
+			save(&p, &body)?;
+			Ok(body)
+		}
+	}
+}
+
+/// Typed result of `live`, recorded as its JSON. For upstream calls whose raw body a library hides.
+pub async fn tape<R: Serialize + DeserializeOwned>(key: &str, live: impl Future<Output = Result<R>>) -> Result<R> {
+	match mode() {
+		Mode::Live => live.await,
+		_ => {
+			let body = http(key, async { Ok(serde_json::to_string_pretty(&live.await?)?) }).await?;
+			serde_json::from_str(&body).wrap_err_with(|| format!("malformed recording for `{key}`"))
+		}
+	}
+}
+
+fn save(p: &Path, body: &str) -> Result<()> {
+	let dir = p.parent().ok_or_else(|| eyre!("cassette path {} has no parent", p.display()))?;
+	std::fs::create_dir_all(dir)?;
+	std::fs::write(p, body).wrap_err_with(|| format!("failed recording {}", p.display()))?;
+	tracing::info!("recorded {}", p.display());
+	Ok(())
+}
+
+/// Keys are `/`-separated; anything outside `[A-Za-z0-9._=-]` within a segment becomes `_`.
+fn path(key: &str) -> PathBuf {
+	let sanitized: String = key
+		.chars()
+		.map(|c| if c.is_ascii_alphanumeric() || matches!(c, '/' | '.' | '_' | '=' | '-') { c } else { '_' })
+		.collect();
+	let file = format!("{}.json", sanitized.replace("..", "_"));
+	#[cfg(test)]
+	if REPLAYING.try_with(|_| ()).is_ok() {
+		return Path::new(FIXTURES).join(file);
+	}
+	DIR.join(file)
+}
@@ -0,0 +1,132 @@
+//! Per-poll history. Every successful refresh of a source that keeps history also lands as a one-row
+//! Arrow IPC segment under `$XDG_DATA_HOME/dashboards/history/<id>/<fetched_at_ms>.arrow`, so the
+//! latest-copy overwrite in [`super::write`] doesn't lose the past. The payload column is the source's
+//! JSON: sources are arbitrary serde types with no fixed columnar shape, and a segment is only ever
+//! read back whole. Retention defaults to `history_retention_days`, and is shorter where polls are frequent
+//! or bulky, so a directory stays small enough to list on every append and read.
+use std::{fs::File, ops::RangeBounds, path::PathBuf, sync::Arc};
+
+use arrow::{
+	array::{Array, StringArray, TimestampMillisecondArray},
+	datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit},
+	ipc::{reader::FileReader, writer::FileWriter},
+	record_batch::RecordBatch,
+};
+use color_eyre::eyre::{Result, eyre};
+use jiff::{SignedDuration, Timestamp};
+
+use super::SourceData;
+
+/// One historical poll of a source.
+pub struct Snapshot<T> {
+	pub fetched_at: Timestamp,
+	pub data: T,
+}
+
+/// Persist `data` as its own segment, then drop segments older than `retention`.
+pub(super) fn append<T: SourceData>(id: &str, fetched_at: Timestamp, data: &T, retention: SignedDuration) -> Result<()> {
+	let dir = dir(id)?;
+	let ms = fetched_at.as_millisecond();
+	let batch = RecordBatch::try_new(
+		schema(),
+		vec![
+			Arc::new(TimestampMillisecondArray::from(vec![ms]).with_timezone("UTC")),
+			Arc::new(StringArray::from(vec![serde_json::to_string(data)?])),
+		],
+	)?;
+	let mut writer = FileWriter::try_new(File::create(dir.join(format!("{ms}.arrow")))?, &batch.schema())?;
+	writer.write(&batch)?;
+	writer.finish()?;
+
+	prune(&dir, fetched_at - retention);
+	Ok(())
+}
+
+/// Every persisted poll with `fetched_at` inside `range`, oldest first. A segment that fails to read
+/// is skipped with a warning — one torn write shouldn't blank the whole history.
+pub(super) fn read<T: SourceData>(id: &str, range: impl RangeBounds<Timestamp>) -> Result<Vec<Snapshot<T>>> {
+	let dir = dir(id)?;
+	let mut out = Vec::new();
+	for (ts, p) in segments(&dir)? {
+		if !range.contains(&ts) {
+			continue;
+		}
+		match read_segment::<T>(&p) {
+			Ok(snaps) => out.extend(snaps),
+			Err(e) => tracing::warn!("skipping corrupt history segment {}: {e}", p.display()),
+		}
+	}
+	out.sort_by_key(|s| s.fetched_at);
+	Ok(out)
+}
+
+fn read_segment<T: SourceData>(p: &std::path::Path) -> Result<Vec<Snapshot<T>>> {
+	let mut out = Vec::new();
+	for batch in FileReader::try_new(File::open(p)?, None)? {
+		let batch = batch?;
+		let times = batch
+			.column(0)
+			.as_any()
+			.downcast_ref::<TimestampMillisecondArray>()
+			.ok_or_else(|| eyre!("`fetched_at` column is not a millisecond timestamp"))?;
+		let payloads = batch.column(1).as_any().downcast_ref::<StringArray>().ok_or_else(|| eyre!("`data` column is not utf8"))?;
+		for i in 0..batch.num_rows() {
+			out.push(Snapshot {
+				fetched_at: Timestamp::from_millisecond(times.value(i))?,
+				data: serde_json::from_str(payloads.value(i))?,
+			});
+		}
+	}
+	Ok(out)
+}
+
+/// Every segment of `id`, along with its directory.
+pub(super) fn remove(id: &str) -> std::io::Result<()> {
+	std::fs::remove_dir_all(v_utils::xdg_data_dir!("dashboards").join("history").join(id))
+}
+
+fn prune(dir: &std::path::Path, cutoff: Timestamp) {
+	let segments = match segments(dir) {
+		Ok(s) => s,
+		Err(e) => {
+			tracing::warn!("failed listing history {}: {e}", dir.display());
+			return;
+		}
+	};
+	for (ts, p) in segments {
+		if ts < cutoff {
+			if let Err(e) = std::fs::remove_file(&p) {
+				tracing::warn!("failed pruning history segment {}: {e}", p.display());
+			}
+		}
+	}
+}
+
+/// `(fetched_at, path)` of every segment in `dir`; the timestamp is read off the filename.
+fn segments(dir: &std::path::Path) -> Result<Vec<(Timestamp, PathBuf)>> {
+	let mut out = Vec::new();
+	for entry in std::fs::read_dir(dir)? {
+		let p = entry?.path();
+		if p.extension().is_none_or(|e| e != "arrow") {
+			continue;
+		}
+		let Some(ms) = p.file_stem().and_then(|s| s.to_str()).and_then(|s| s.parse::<i64>().ok()) else {
+			continue;
+		};
+		out.push((Timestamp::from_millisecond(ms)?, p));
+	}
+	Ok(out)
+}
+
+fn dir(id: &str) -> Result<PathBuf> {
+	let dir = v_utils::xdg_data_dir!("dashboards").join("history").join(id);
+	std::fs::create_dir_all(&dir)?;
+	Ok(dir)
+}
+
+fn schema() -> SchemaRef {
+	Arc::new(Schema::new(vec![
+		Field::new("fetched_at", DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())), false),
+		Field::new("data", DataType::Utf8, false),
+	]))
+}
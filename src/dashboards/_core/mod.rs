@@ -1,33 +1,77 @@
 //! Standardizes polled data sources: persist every poll to `$XDG_DATA_HOME`, and
 //! don't hit upstream again until the persisted copy is older than [`SourceData::decay_horizon`].
+//! Each poll is also appended to a per-source history (see [`history`]), read back via [`load_history`];
+//! sources bound or opt out of it through [`SourceData::history_retention`].
+//! The [`Scheduler`] keeps every registered source warm in the background; `load` refetching on its
+//! own is the fallback for a cold start or a failed pre-warm. Refreshes and fetch progress are
+//! broadcast as [`SourceEvent`]s, which `/data/events` pushes to the browser.
+//! A source may come in several variants ([`SourceData::Params`]); each is cached, locked, refreshed
+//! and historied on its own, under its [`id`].
+pub mod cassette;
+mod history;
+mod scheduler;
+
 use std::{
-	collections::HashMap,
+	collections::{HashMap, HashSet},
 	future::Future,
-	path::PathBuf,
+	ops::RangeBounds,
+	path::{Path, PathBuf},
 	sync::{Arc, LazyLock, Mutex},
 };
 
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{Result, WrapErr as _, eyre};
 use futures::lock::Mutex as AsyncMutex;
 use jiff::{SignedDuration, Timestamp};
 use serde::{Serialize, de::DeserializeOwned};
+use tokio::sync::broadcast;
 use v_utils::trades::Timeframe;
 
+pub use self::{
+	history::Snapshot,
+	scheduler::{Scheduler, with_settings},
+};
+use super::SourceEvent;
+
+/// Selects one variant of a source (exchange, instrument, …). `()` for sources that have just the one.
+pub trait SourceParams: Clone + Default + Send + Sync + 'static {
+	/// Stable, filesystem-safe suffix of this variant's [`id`]. Empty means the bare source name.
+	fn key(&self) -> String;
+}
+impl SourceParams for () {
+	fn key(&self) -> String {
+		String::new()
+	}
+}
+
 pub trait SourceData: Sized + Serialize + DeserializeOwned + Send {
+	type Params: SourceParams;
+
 	/// Refresh interval: once the persisted copy is older than this, the next `load` repolls.
-	/// Staleness (the client-facing warning) is a longer horizon — see [`load`].
+	/// Staleness (the client-facing warning) is a longer horizon — see [`load_with`].
 	fn decay_horizon() -> Timeframe;
-	fn fetch() -> impl Future<Output = Result<Self>> + Send;
+	fn fetch(params: &Self::Params) -> impl Future<Output = Result<Self>> + Send;
 
 	fn name() -> &'static str {
 		let p = std::any::type_name::<Self>();
 		p.rsplit("::").next().unwrap_or(p)
 	}
 
-	/// How an in-flight fetch's `(loaded, target)` is rendered to the client poller.
+	/// How long each poll is kept in history, read back via [`load_history_with`]; capped at `history_retention_days`,
+	/// which is also the default. Frequent or bulky sources keep only as far back as they read; `None` keeps none.
+	fn history_retention() -> Option<SignedDuration> {
+		Some(SignedDuration::MAX)
+	}
+
+	/// How an in-flight fetch's `(loaded, target)` is rendered in pushed progress ticks.
 	fn fmt_progress(loaded: usize, target: usize) -> String {
 		format!("{loaded}/{target}")
 	}
+
+	/// Deterministic stand-in served under `mock` when nothing is persisted yet, so the panel renders
+	/// with no network. A recorded `fixtures/dashboards/<id>.json` takes precedence — see [`fixture`].
+	fn fixture(_params: &Self::Params) -> Option<Self> {
+		None
+	}
 }
 
 /// Live per-source fetch progress, opted into by multi-pair sources inside their `fetch()`.
@@ -36,28 +80,42 @@ pub struct Progress {
 	name: &'static str,
 }
 impl Progress {
-	pub fn of<T: SourceData>() -> Self {
-		Self { name: T::name() }
+	pub fn of<T: SourceData>(params: &T::Params) -> Self {
+		Self { name: id::<T>(params) }
 	}
 
 	pub fn set_target(&self, target: usize) {
-		if let Some(e) = REGISTRY.lock().unwrap().get_mut(self.name) {
-			e.target = target;
-		}
+		self.update(|e| e.target = target);
 	}
 
 	pub fn inc(&self) {
-		if let Some(e) = REGISTRY.lock().unwrap().get_mut(self.name) {
-			e.loaded += 1;
-		}
+		self.update(|e| e.loaded += 1);
+	}
+
+	fn update(&self, f: impl FnOnce(&mut Entry)) {
+		let progress = {
+			let mut reg = REGISTRY.lock().unwrap();
+			let Some(e) = reg.get_mut(self.name) else { return };
+			f(e);
+			(e.fmt)(e.loaded, e.target)
+		};
+		publish(SourceEvent::Progress {
+			source: self.name.to_owned(),
+			progress,
+		});
 	}
 }
 
-/// Formatted progress of an in-flight fetch, or `None` when nothing is loading under `name`.
-pub fn progress_of(name: &str) -> Option<String> {
-	let reg = REGISTRY.lock().unwrap();
-	reg.get(name).map(|e| (e.fmt)(e.loaded, e.target))
+/// Live feed of every source refresh and progress tick. A receiver that falls behind by more than
+/// the channel capacity skips the backlog (`RecvError::Lagged`) rather than stalling publishers.
+pub fn subscribe() -> broadcast::Receiver<SourceEvent> {
+	EVENTS.subscribe()
 }
+fn publish(event: SourceEvent) {
+	// `Err` only means nobody is listening right now
+	let _ = EVENTS.send(event);
+}
+static EVENTS: LazyLock<broadcast::Sender<SourceEvent>> = LazyLock::new(|| broadcast::channel(256).0);
 /// A loaded source, plus a marker when the served copy is old enough to surface as stale.
 pub struct Loaded<T> {
 	pub data: T,
@@ -73,23 +131,28 @@ pub struct Stale {
 /// rather than each fanning out. If the refresh fails, still serve the persisted copy — flagged
 /// [`Stale`] only once it's older than `interval × stale_multiplier`, so a blip inside the grace
 /// window stays quiet.
-pub async fn load<T: SourceData>() -> Result<Loaded<T>> {
+pub async fn load_with<T: SourceData>(params: &T::Params) -> Result<Loaded<T>> {
+	let id = id::<T>(params);
 	let interval = T::decay_horizon().duration();
 
 	// Fast path: a fresh (or mock) copy needs neither the lock nor a poll.
-	if let Some(c) = read::<T>() {
+	if let Some(c) = read::<T>(id) {
 		let age = Timestamp::now().duration_since(c.fetched_at);
 		if mock_enabled() || !past(age, interval) {
 			return Ok(Loaded { data: c.data, stale: None });
 		}
 	}
+	// Mock never reaches upstream: a cold cache is served a fixture instead.
+	if mock_enabled() {
+		return fixture::<T>(params).map(|data| Loaded { data, stale: None });
+	}
 
 	// Refresh due — hold the source lock across the poll so a second caller queues behind us and,
 	// on waking, finds the copy we just wrote instead of launching its own fan-out.
-	let lock = source_lock::<T>();
+	let lock = source_lock(id);
 	let _guard = lock.lock().await;
 
-	let cached = read::<T>();
+	let cached = read::<T>(id);
 	let now_fresh = cached.as_ref().is_some_and(|c| !past(Timestamp::now().duration_since(c.fetched_at), interval));
 	if now_fresh {
 		return Ok(Loaded {
@@ -98,9 +161,9 @@ pub async fn load<T: SourceData>() -> Result<Loaded<T>> {
 		});
 	}
 
-	match fetch_tracked::<T>().await {
+	match fetch_tracked::<T>(params).await {
 		Ok(data) => {
-			write(&data)?;
+			write(id, &data)?;
 			Ok(Loaded { data, stale: None })
 		}
 		Err(e) => match cached {
@@ -112,8 +175,8 @@ pub async fn load<T: SourceData>() -> Result<Loaded<T>> {
 					error: e.to_string(),
 				});
 				match &stale {
-					Some(_) => tracing::warn!("refetch of {} failed ({e}); serving stale copy from {}", T::name(), c.fetched_at),
-					None => tracing::debug!("refetch of {} failed ({e}); serving copy from {} (within grace)", T::name(), c.fetched_at),
+					Some(_) => tracing::warn!("refetch of {id} failed ({e}); serving stale copy from {}", c.fetched_at),
+					None => tracing::debug!("refetch of {id} failed ({e}); serving copy from {} (within grace)", c.fetched_at),
 				}
 				Ok(Loaded { data: c.data, stale })
 			}
@@ -121,28 +184,110 @@ pub async fn load<T: SourceData>() -> Result<Loaded<T>> {
 		},
 	}
 }
+/// [`load_with`] the source's default variant.
+pub async fn load<T: SourceData>() -> Result<Loaded<T>> {
+	load_with::<T>(&T::Params::default()).await
+}
+
+/// The persisted copy of this variant of `T` however old, without polling: for a `fetch` that tops up its own
+/// previous result rather than refetching all of it. `fetch` already runs under the source's lock.
+pub fn persisted_with<T: SourceData>(params: &T::Params) -> Option<T> {
+	read::<T>(id::<T>(params)).map(|c| c.data)
+}
+
+/// Persist `data` as this variant of `T`'s fresh copy, outside its own `fetch`: for a source whose variants another
+/// source's poll fills in as a batch. Takes the variant's lock, so it can't interleave with that variant's own refresh.
+pub async fn persist_with<T: SourceData>(params: &T::Params, data: &T) -> Result<Timestamp> {
+	let id = id::<T>(params);
+	let lock = source_lock(id);
+	let _guard = lock.lock().await;
+	write(id, data)
+}
+
+/// Drop the persisted copy and history of an id no source answers to anymore, e.g. a source's bare name once it
+/// took variants. Nothing there is fine.
+pub fn retire(id: &str) {
+	for removed in [std::fs::remove_file(path(id)), history::remove(id)] {
+		if let Err(e) = removed {
+			if e.kind() != std::io::ErrorKind::NotFound {
+				tracing::warn!("failed removing retired source {id}: {e}");
+			}
+		}
+	}
+}
+
+/// Every persisted poll of this variant of `T` fetched inside `range`, oldest first. Only polls within
+/// [`SourceData::history_retention`] are available, so this is empty for sources that opt out.
+pub fn load_history_with<T: SourceData>(params: &T::Params, range: impl RangeBounds<Timestamp>) -> Result<Vec<Snapshot<T>>> {
+	history::read::<T>(id::<T>(params), range)
+}
+/// [`load_history_with`] the source's default variant.
+pub fn load_history<T: SourceData>(range: impl RangeBounds<Timestamp>) -> Result<Vec<Snapshot<T>>> {
+	load_history_with::<T>(&T::Params::default(), range)
+}
+
+/// Scheduler entry: poll `T` once its persisted copy is within `lead` of the refresh interval, so the
+/// refresh lands before a visitor would trigger one. Shares [`load_with`]'s per-source lock, so a
+/// request-driven refresh already in flight is joined rather than repeated. Returns when the
+/// now-persisted copy was fetched.
+async fn prewarm<T: SourceData>(params: &T::Params, lead: std::time::Duration) -> Result<Timestamp> {
+	let id = id::<T>(params);
+	let interval = T::decay_horizon().duration();
+	let lock = source_lock(id);
+	let _guard = lock.lock().await;
+
+	if let Some(c) = read::<T>(id) {
+		if !past(Timestamp::now().duration_since(c.fetched_at), interval.saturating_sub(lead)) {
+			return Ok(c.fetched_at);
+		}
+	}
+	let data = fetch_tracked::<T>(params).await?;
+	write(id, &data)
+}
+
+/// Cache, lock and event name of one variant of `T`: [`SourceData::name`], suffixed `-<key>` unless the
+/// key is empty. Interned, so [`Progress`] stays `Copy` and the registries keep `&'static str` keys.
+pub fn id<T: SourceData>(params: &T::Params) -> &'static str {
+	let key = params.key();
+	if key.is_empty() {
+		return T::name();
+	}
+	let id = format!("{}-{key}", T::name());
+	let mut ids = IDS.lock().unwrap();
+	if let Some(&interned) = ids.get(id.as_str()) {
+		return interned;
+	}
+	let interned: &'static str = Box::leak(id.into_boxed_str());
+	ids.insert(interned);
+	interned
+}
+// ponytail: leaks one string per distinct variant; params are closed sets, or grow as slowly as the listed pairs do,
+// so this stays bounded
+static IDS: LazyLock<Mutex<HashSet<&'static str>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
 /// At/over `horizon` — or negative, since a future `fetched_at` (clock skew) should count as due.
 fn past(age: SignedDuration, horizon: std::time::Duration) -> bool {
 	age.is_negative() || age.unsigned_abs() >= horizon
 }
 
 /// Per-source async lock, created on first use, so `load` coalesces concurrent refreshes into one.
-fn source_lock<T: SourceData>() -> Arc<AsyncMutex<()>> {
-	LOCKS.lock().unwrap().entry(T::name()).or_insert_with(|| Arc::new(AsyncMutex::new(()))).clone()
+fn source_lock(id: &'static str) -> Arc<AsyncMutex<()>> {
+	LOCKS.lock().unwrap().entry(id).or_insert_with(|| Arc::new(AsyncMutex::new(()))).clone()
 }
 static LOCKS: LazyLock<Mutex<HashMap<&'static str, Arc<AsyncMutex<()>>>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
 
-async fn fetch_tracked<T: SourceData>() -> Result<T> {
+async fn fetch_tracked<T: SourceData>(params: &T::Params) -> Result<T> {
+	let id = id::<T>(params);
 	REGISTRY.lock().unwrap().insert(
-		T::name(),
+		id,
 		Entry {
 			loaded: 0,
 			target: 0,
 			fmt: T::fmt_progress,
 		},
 	);
-	let result = T::fetch().await;
-	REGISTRY.lock().unwrap().remove(T::name());
+	let result = T::fetch(params).await;
+	REGISTRY.lock().unwrap().remove(id);
 	result
 }
 struct Entry {
@@ -169,8 +314,35 @@ fn stale_multiplier() -> f64 {
 		.stale_multiplier()
 }
 
-fn path<T: SourceData>() -> PathBuf {
-	v_utils::xdg_data_dir!("dashboards").join(format!("{}.json", T::name()))
+/// Recorded fixtures: the raw JSON of a source's data (the `data` field of its persisted copy, so
+/// a warm `$XDG_DATA_HOME/dashboards/<id>.json` can be re-recorded by copying that field out).
+const FIXTURES_DIR: &str = "fixtures/dashboards";
+
+/// Mock-mode data for a cold cache: `fixtures/dashboards/<id>.json` if checked in, else the
+/// source's generated [`SourceData::fixture`].
+fn fixture<T: SourceData>(params: &T::Params) -> Result<T> {
+	let id = id::<T>(params);
+	let p = Path::new(FIXTURES_DIR).join(format!("{id}.json"));
+	match std::fs::read_to_string(&p) {
+		Ok(s) => return serde_json::from_str(&s).wrap_err_with(|| format!("malformed fixture {}", p.display())),
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+		Err(e) => return Err(e).wrap_err_with(|| format!("failed reading fixture {}", p.display())),
+	}
+	T::fixture(params).ok_or_else(|| eyre!("mock is on, but nothing is persisted for {id} and it has no fixture"))
+}
+
+/// Ceiling on every source's [`SourceData::history_retention`].
+fn history_cap() -> SignedDuration {
+	let days = leptos::prelude::use_context::<crate::config::LiveSettings>()
+		.expect("LiveSettings in context")
+		.config()
+		.expect("config loads")
+		.history_retention_days();
+	SignedDuration::from_hours(days as i64 * 24)
+}
+
+fn path(id: &str) -> PathBuf {
+	v_utils::xdg_data_dir!("dashboards").join(format!("{id}.json"))
 }
 
 #[derive(serde::Deserialize)]
@@ -184,8 +356,8 @@ struct CachedRef<'a, T> {
 	data: &'a T,
 }
 
-fn read<T: SourceData>() -> Option<Cached<T>> {
-	let p = path::<T>();
+fn read<T: SourceData>(id: &str) -> Option<Cached<T>> {
+	let p = path(id);
 	match std::fs::read_to_string(&p) {
 		Ok(s) => match serde_json::from_str(&s) {
 			Ok(c) => Some(c),
@@ -202,10 +374,20 @@ fn read<T: SourceData>() -> Option<Cached<T>> {
 	}
 }
 
-fn write<T: SourceData>(data: &T) -> Result<()> {
-	let record = CachedRef { fetched_at: Timestamp::now(), data };
-	let p = path::<T>();
+/// Overwrite the latest copy, then append the same poll to history if `T` keeps one. History is best-effort:
+/// a failed append is logged, never allowed to fail the load that produced fresh data.
+fn write<T: SourceData>(id: &str, data: &T) -> Result<Timestamp> {
+	let fetched_at = Timestamp::now();
+	let record = CachedRef { fetched_at, data };
+	let p = path(id);
 	std::fs::write(&p, serde_json::to_string_pretty(&record)?)?;
-	tracing::info!("persisted {}", T::name());
-	Ok(())
+	tracing::info!("persisted {id}");
+	publish(SourceEvent::Refreshed { source: id.to_owned(), fetched_at });
+
+	if let Some(retention) = T::history_retention() {
+		if let Err(e) = history::append(id, fetched_at, data, retention.min(history_cap())) {
+			tracing::warn!("failed appending {id} to history: {e}");
+		}
+	}
+	Ok(fetched_at)
 }
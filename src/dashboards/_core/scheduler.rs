@@ -0,0 +1,164 @@
+//! Background pre-warm. Each registered source gets its own task that re-polls slightly ahead of its
+//! `decay_horizon` (a random lead of up to [`JITTER`] of the interval, so sources sharing a horizon
+//! don't fire in lockstep), gated by one semaphore so the combined upstream fan-out stays bounded. Cheap
+//! single-call sources on a short horizon can [skip it](Scheduler::register_cheap_with), so they aren't left
+//! queuing behind the fan-outs.
+//! Long-lived upstream connections (websocket feeds) are registered as [streams](Scheduler::stream):
+//! supervised tasks outside that budget, reconnected with backoff whenever they drop.
+use std::{
+	future::Future,
+	hash::{BuildHasher as _, RandomState},
+	sync::Arc,
+	time::Duration,
+};
+
+use futures::future::BoxFuture;
+use jiff::Timestamp;
+use leptos::{
+	prelude::provide_context,
+	reactive::{computed::ScopedFuture, owner::Owner},
+};
+use tokio::sync::Semaphore;
+
+use super::SourceData;
+use crate::config::LiveSettings;
+
+/// Largest fraction of a source's refresh interval a pre-warm may run ahead of expiry.
+const JITTER: f64 = 0.1;
+/// Wait before retrying a failed pre-warm; capped by the source's own interval.
+const RETRY: Duration = Duration::from_secs(60);
+/// Ceiling of a stream's reconnect backoff, which doubles from [`RETRY`] on each consecutive drop.
+const STREAM_BACKOFF_MAX: Duration = Duration::from_secs(15 * 60);
+/// A stream that stayed up this long before dropping counts as healthy: its backoff starts over.
+const STREAM_HEALTHY: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Clone)]
+struct Ctx {
+	settings: LiveSettings,
+	permits: Arc<Semaphore>,
+}
+
+type Job = Box<dyn FnOnce(Ctx) -> BoxFuture<'static, ()>>;
+
+/// Registry of sources to keep warm. Built once at startup, then [`spawn`](Self::spawn)ed.
+#[derive(Default)]
+pub struct Scheduler {
+	jobs: Vec<Job>,
+}
+impl Scheduler {
+	/// Keep the source's default variant warm.
+	pub fn register<T: SourceData + 'static>(&mut self) -> &mut Self {
+		self.register_with::<T>(T::Params::default())
+	}
+
+	/// Keep one variant warm. Each registered variant is its own task.
+	pub fn register_with<T: SourceData + 'static>(&mut self, params: T::Params) -> &mut Self {
+		self.jobs.push(Box::new(move |ctx| Box::pin(run::<T>(ctx, params, true))));
+		self
+	}
+
+	/// [`register_with`](Self::register_with), outside the refresh budget: for a source that's one light upstream
+	/// call, polled too often to wait out a multi-minute fan-out for a permit.
+	pub fn register_cheap_with<T: SourceData + 'static>(&mut self, params: T::Params) -> &mut Self {
+		self.jobs.push(Box::new(move |ctx| Box::pin(run::<T>(ctx, params, false))));
+		self
+	}
+
+	/// Keep a long-lived upstream connection running for as long as the server does. `connect` runs it until it
+	/// ends or fails, and is called afresh to reconnect. Streams hold no refresh permit: they mostly wait on the
+	/// wire, and reach clients only through whatever source they feed.
+	pub fn stream<F>(&mut self, name: &'static str, connect: impl Fn() -> F + Send + 'static) -> &mut Self
+	where
+		F: Future<Output = color_eyre::eyre::Result<()>> + Send + 'static, {
+		self.jobs.push(Box::new(move |ctx| Box::pin(supervise(ctx, name, connect))));
+		self
+	}
+
+	/// One task per registered source and stream. The concurrency budget is read once here; changing
+	/// `refresh_concurrency` takes a restart.
+	pub fn spawn(self, settings: LiveSettings) {
+		let concurrency = settings.config().map(|s| s.refresh_concurrency()).unwrap_or(1);
+		let ctx = Ctx {
+			settings,
+			permits: Arc::new(Semaphore::new(concurrency)),
+		};
+		for job in self.jobs {
+			tokio::spawn(job(ctx.clone()));
+		}
+	}
+}
+
+async fn run<T: SourceData>(ctx: Ctx, params: T::Params, budgeted: bool) {
+	let id = super::id::<T>(&params);
+	let interval = T::decay_horizon().duration();
+	loop {
+		let lead = interval.mul_f64(JITTER * unit_random());
+		let wait = {
+			let _permit = if budgeted {
+				Some(ctx.permits.acquire().await.expect("semaphore is never closed"))
+			} else {
+				None
+			};
+			match with_settings(&ctx.settings, tick::<T>(&params, lead)).await {
+				Ok(Some(fetched_at)) => {
+					let age = Timestamp::now().duration_since(fetched_at).unsigned_abs();
+					interval.saturating_sub(lead).saturating_sub(age)
+				}
+				// mock serves the persisted copy regardless of age — nothing to keep warm
+				Ok(None) => interval,
+				Err(e) => {
+					tracing::warn!("pre-warm of {id} failed: {e}; retrying");
+					RETRY.min(interval)
+				}
+			}
+		};
+		tokio::time::sleep(wait).await;
+	}
+}
+
+async fn supervise<F: Future<Output = color_eyre::eyre::Result<()>>>(ctx: Ctx, name: &'static str, connect: impl Fn() -> F) {
+	let mut backoff = RETRY;
+	loop {
+		// mock never reaches upstream; look again later in case it's switched off
+		if with_settings(&ctx.settings, async { super::mock_enabled() }).await {
+			tokio::time::sleep(RETRY).await;
+			continue;
+		}
+		let started = std::time::Instant::now();
+		match with_settings(&ctx.settings, connect()).await {
+			Ok(()) => tracing::warn!("stream {name} ended; reconnecting in {backoff:?}"),
+			Err(e) => tracing::warn!("stream {name} failed: {e}; reconnecting in {backoff:?}"),
+		}
+		if started.elapsed() >= STREAM_HEALTHY {
+			backoff = RETRY;
+		}
+		tokio::time::sleep(backoff).await;
+		backoff = (backoff * 2).min(STREAM_BACKOFF_MAX);
+	}
+}
+
+async fn tick<T: SourceData>(params: &T::Params, lead: Duration) -> color_eyre::eyre::Result<Option<Timestamp>> {
+	if super::mock_enabled() {
+		return Ok(None);
+	}
+	super::prewarm::<T>(params, lead).await.map(Some)
+}
+
+/// `load` and friends resolve `LiveSettings` from the reactive context, same as under a server fn; this
+/// provides it to `fut` outside of one.
+pub async fn with_settings<F: Future>(settings: &LiveSettings, fut: F) -> F::Output {
+	let settings = settings.clone();
+	Owner::new()
+		.with(|| {
+			ScopedFuture::new(async move {
+				provide_context(settings);
+				fut.await
+			})
+		})
+		.await
+}
+
+/// Uniform in `[0, 1)`, off std's per-instance random hasher keys — jitter needs no real RNG.
+fn unit_random() -> f64 {
+	(RandomState::new().hash_one(()) >> 11) as f64 / (1u64 << 53) as f64
+}
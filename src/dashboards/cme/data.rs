@@ -1,116 +1,276 @@
 use color_eyre::eyre::{Result, eyre};
 use jiff::{Timestamp, civil::Date, tz::TimeZone};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_with::{DisplayFromStr, serde_as};
 use v_utils::{NowThen, PrettyPrint};
 
+use super::ReportKind;
+use crate::dashboards::_core::{SourceParams, cassette};
+
+/// Which contract's reports to pull, and from which report: the contract is its CFTC contract market
+/// code, e.g. `133741` for CME Bitcoin.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CftcParams {
+	pub code: String,
+	pub kind: ReportKind,
+}
+impl Default for CftcParams {
+	fn default() -> Self {
+		Self {
+			code: CFTC_CODE_BTC.to_owned(),
+			kind: ReportKind::default(),
+		}
+	}
+}
+impl SourceParams for CftcParams {
+	fn key(&self) -> String {
+		format!("{}-{}", self.code, self.kind.slug())
+	}
+}
+
+impl ReportKind {
+	/// Socrata dataset id on publicreporting.cftc.gov.
+	fn dataset(self) -> &'static str {
+		match self {
+			Self::TffFutures => "gpe5-46if",
+			Self::TffCombined => "yw9f-hn96",
+			Self::LegacyFutures => "6dca-aqww",
+			Self::LegacyCombined => "jun7-fc8e",
+			Self::DisaggregatedFutures => "72hh-3qpy",
+		}
+	}
+
+	fn title(self) -> &'static str {
+		match self {
+			Self::TffFutures => "Traders in Financial Futures - Futures Only",
+			Self::TffCombined => "Traders in Financial Futures - Futures and Options Combined",
+			Self::LegacyFutures => "Commitments of Traders - Futures Only",
+			Self::LegacyCombined => "Commitments of Traders - Futures and Options Combined",
+			Self::DisaggregatedFutures => "Disaggregated Commitments of Traders - Futures Only",
+		}
+	}
+
+	/// The two categories the short summary is about: who's speculating, and who they trade against.
+	fn headline(self) -> [&'static str; 2] {
+		match self {
+			Self::TffFutures | Self::TffCombined => [ASSET_MANAGER, LEVERAGED_FUNDS],
+			Self::LegacyFutures | Self::LegacyCombined => [NONCOMMERCIAL, COMMERCIAL],
+			Self::DisaggregatedFutures => [MANAGED_MONEY, PRODUCER_MERCHANT],
+		}
+	}
+
+	/// Category names in report order, and whether each has a spreading column.
+	fn categories(self) -> &'static [(&'static str, bool)] {
+		match self {
+			Self::TffFutures | Self::TffCombined => &[(DEALER, true), (ASSET_MANAGER, true), (LEVERAGED_FUNDS, true), (OTHER_REPORTABLES, true)],
+			Self::LegacyFutures | Self::LegacyCombined => &[(NONCOMMERCIAL, true), (COMMERCIAL, false), (NONREPORTABLE, false)],
+			Self::DisaggregatedFutures => &[
+				(PRODUCER_MERCHANT, false),
+				(SWAP_DEALERS, true),
+				(MANAGED_MONEY, true),
+				(OTHER_REPORTABLES, true),
+				(NONREPORTABLE, false),
+			],
+		}
+	}
+}
+const DEALER: &str = "Dealer Intermediary";
+const ASSET_MANAGER: &str = "Asset Manager/Institutional";
+const LEVERAGED_FUNDS: &str = "Leveraged Funds";
+const OTHER_REPORTABLES: &str = "Other Reportables";
+const NONCOMMERCIAL: &str = "Non-Commercial";
+const COMMERCIAL: &str = "Commercial";
+const NONREPORTABLE: &str = "Nonreportable";
+const PRODUCER_MERCHANT: &str = "Producer/Merchant";
+const SWAP_DEALERS: &str = "Swap Dealers";
+const MANAGED_MONEY: &str = "Managed Money";
+
 #[allow(unused)]
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, derive_new::new)]
 pub struct Positions {
 	long: PositionsInfo,
 	short: PositionsInfo,
-	spreading: PositionsInfo,
+	/// `None` for categories the report doesn't break spreading out for.
+	spreading: Option<PositionsInfo>,
 }
-#[allow(unused)]
-#[derive(Clone, Debug, Default, Deserialize, Serialize, derive_new::new)]
+impl Positions {
+	/// Contracts long minus contracts short; spreads cancel out.
+	pub fn net(&self) -> f64 {
+		self.long.current - self.short.current
+	}
+
+	fn columns(&self) -> Vec<(&'static str, &PositionsInfo)> {
+		let mut columns = vec![("Long", &self.long), ("Short", &self.short)];
+		columns.extend(self.spreading.as_ref().map(|s| ("Spread", s)));
+		columns
+	}
+}
+/// One week's positions by trader category, whichever report they're from.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct CftcReport {
 	pub date: Timestamp,
-	pub dealer_intermidiary: Positions,
-	pub asset_manager_or_institutional: Positions,
-	pub leveraged_funds: Positions,
-	pub other_reportables: Positions,
+	pub kind: ReportKind,
+	/// In the report's own order.
+	pub categories: Vec<(String, Positions)>,
 }
 impl CftcReport {
+	/// Mock-mode report for any contract and kind: the fixed BTC TFF numbers, dealt out over the kind's
+	/// categories and sized per contract, so every run renders the same panel and switching shows it.
+	pub fn sample_for(params: &CftcParams) -> Self {
+		let info = |current, change, pct, traders| PositionsInfo::new(current, change, pct, traders);
+		let pool = [
+			Positions::new(info(1_512.0, 104.0, 5.1, Some(9)), info(806.0, -61.0, 2.7, Some(7)), Some(info(48.0, 3.0, 0.2, None))),
+			Positions::new(info(9_034.0, 412.0, 30.4, Some(58)), info(1_487.0, 95.0, 5.0, Some(14)), Some(info(301.0, -22.0, 1.0, None))),
+			Positions::new(
+				info(6_120.0, -380.0, 20.6, Some(31)),
+				info(17_245.0, 655.0, 58.1, Some(44)),
+				Some(info(912.0, 40.0, 3.1, Some(11))),
+			),
+			Positions::new(info(2_488.0, 57.0, 8.4, Some(23)), info(1_204.0, -18.0, 4.1, Some(10)), Some(info(149.0, 6.0, 0.5, None))),
+		];
+		let k = match params.code.as_str() {
+			CFTC_CODE_BTC => 1.0,
+			code => 0.25 + (code.bytes().fold(0u32, |h, b| h.wrapping_mul(31).wrapping_add(b as u32)) % 400) as f64 / 100.0,
+		};
+		let scale = |i: PositionsInfo| PositionsInfo {
+			current: (i.current * k).round(),
+			change_since_last_week: (i.change_since_last_week * k).round(),
+			..i
+		};
+		Self {
+			date: Timestamp::from_second(1_759_865_400).expect("in range"), // Tue 2025-10-07 15:30 ET
+			kind: params.kind,
+			categories: params
+				.kind
+				.categories()
+				.iter()
+				.zip(pool.into_iter().cycle())
+				.map(|((name, has_spread), p)| {
+					let spreading = if *has_spread { p.spreading.map(scale) } else { None };
+					(name.to_string(), Positions::new(scale(p.long), scale(p.short), spreading))
+				})
+				.collect(),
+		}
+	}
+
 	pub fn to_markdown_table(&self) -> String {
-		let format_num = |n: f64| format!("{n:.0}");
-		let format_pct = |n: f64| format!("{n:.1}");
-		let format_trader = |n: Option<u32>| n.map_or(".".to_string(), |v| v.to_string());
-
-		let date_str = self.date.strftime("%B %d, %Y").to_string();
-
-		format!(
-			"# Traders in Financial Futures - Futures Only Positions as of {}\n\n\
-           |Position Type|Dealer Intermediary|||Asset Manager/Institutional|||Leveraged Funds|||Other Reportables|||\n\
-           |------------|-------------------|---|---|----------------------|---|---|--------------|---|---|-----------------|---|---|\n\
-           ||Long|Short|Spread|Long|Short|Spread|Long|Short|Spread|Long|Short|Spread|\n\
-           |**Current**|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|\n\
-           |**Changes**|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|\n\
-           |**% of Open**|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|\n\
-           |**Traders**|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|",
-			date_str,
-			// Current positions
-			format_num(self.dealer_intermidiary.long.current),
-			format_num(self.dealer_intermidiary.short.current),
-			format_num(self.dealer_intermidiary.spreading.current),
-			format_num(self.asset_manager_or_institutional.long.current),
-			format_num(self.asset_manager_or_institutional.short.current),
-			format_num(self.asset_manager_or_institutional.spreading.current),
-			format_num(self.leveraged_funds.long.current),
-			format_num(self.leveraged_funds.short.current),
-			format_num(self.leveraged_funds.spreading.current),
-			format_num(self.other_reportables.long.current),
-			format_num(self.other_reportables.short.current),
-			format_num(self.other_reportables.spreading.current),
-			// Changes
-			format_num(self.dealer_intermidiary.long.change_since_last_week),
-			format_num(self.dealer_intermidiary.short.change_since_last_week),
-			format_num(self.dealer_intermidiary.spreading.change_since_last_week),
-			format_num(self.asset_manager_or_institutional.long.change_since_last_week),
-			format_num(self.asset_manager_or_institutional.short.change_since_last_week),
-			format_num(self.asset_manager_or_institutional.spreading.change_since_last_week),
-			format_num(self.leveraged_funds.long.change_since_last_week),
-			format_num(self.leveraged_funds.short.change_since_last_week),
-			format_num(self.leveraged_funds.spreading.change_since_last_week),
-			format_num(self.other_reportables.long.change_since_last_week),
-			format_num(self.other_reportables.short.change_since_last_week),
-			format_num(self.other_reportables.spreading.change_since_last_week),
-			// Percentages
-			format_pct(self.dealer_intermidiary.long.percent_of_open),
-			format_pct(self.dealer_intermidiary.short.percent_of_open),
-			format_pct(self.dealer_intermidiary.spreading.percent_of_open),
-			format_pct(self.asset_manager_or_institutional.long.percent_of_open),
-			format_pct(self.asset_manager_or_institutional.short.percent_of_open),
-			format_pct(self.asset_manager_or_institutional.spreading.percent_of_open),
-			format_pct(self.leveraged_funds.long.percent_of_open),
-			format_pct(self.leveraged_funds.short.percent_of_open),
-			format_pct(self.leveraged_funds.spreading.percent_of_open),
-			format_pct(self.other_reportables.long.percent_of_open),
-			format_pct(self.other_reportables.short.percent_of_open),
-			format_pct(self.other_reportables.spreading.percent_of_open),
-			// Traders
-			format_trader(self.dealer_intermidiary.long.number_of_traders),
-			format_trader(self.dealer_intermidiary.short.number_of_traders),
-			format_trader(self.dealer_intermidiary.spreading.number_of_traders),
-			format_trader(self.asset_manager_or_institutional.long.number_of_traders),
-			format_trader(self.asset_manager_or_institutional.short.number_of_traders),
-			format_trader(self.asset_manager_or_institutional.spreading.number_of_traders),
-			format_trader(self.leveraged_funds.long.number_of_traders),
-			format_trader(self.leveraged_funds.short.number_of_traders),
-			format_trader(self.leveraged_funds.spreading.number_of_traders),
-			format_trader(self.other_reportables.long.number_of_traders),
-			format_trader(self.other_reportables.short.number_of_traders),
-			format_trader(self.other_reportables.spreading.number_of_traders),
-		)
+		let format_num = |i: &PositionsInfo| format!("{:.0}", i.current);
+		let format_change = |i: &PositionsInfo| format!("{:.0}", i.change_since_last_week);
+		let format_pct = |i: &PositionsInfo| format!("{:.1}", i.percent_of_open);
+		let format_trader = |i: &PositionsInfo| i.number_of_traders.map_or(".".to_string(), |v| v.to_string());
+
+		let columns: Vec<Vec<(&str, &PositionsInfo)>> = self.categories.iter().map(|(_, p)| p.columns()).collect();
+		let mut header = String::from("|Position Type|");
+		let mut rule = String::from("|------------|");
+		let mut sides = String::from("||");
+		for ((name, _), cols) in self.categories.iter().zip(&columns) {
+			header.push_str(&format!("{name}|{}", "|".repeat(cols.len() - 1)));
+			rule.push_str(&"---|".repeat(cols.len()));
+			sides.extend(cols.iter().map(|(side, _)| format!("{side}|")));
+		}
+		let row = |label: &str, cell: &dyn Fn(&PositionsInfo) -> String| format!("|**{label}**|{}", columns.iter().flatten().map(|(_, i)| format!("{}|", cell(i))).collect::<String>());
+
+		[
+			format!("# {} Positions as of {}\n", self.kind.title(), self.date.strftime("%B %d, %Y")),
+			header,
+			rule,
+			sides,
+			row("Current", &format_num),
+			row("Changes", &format_change),
+			row("% of Open", &format_pct),
+			row("Traders", &format_trader),
+		]
+		.join("\n")
 	}
 }
 
 // www.cftc.gov's HTML report sits behind a Cloudflare bot-challenge that 403s datacenter/pod egress
-// IPs; the Socrata data API serves the same "Traders in Financial Futures" report as JSON with no challenge.
-pub async fn fetch_cftc_positions() -> Result<CftcReport> {
-	let rows: Vec<TffRow> = reqwest::Client::new()
-		.get("https://publicreporting.cftc.gov/resource/gpe5-46if.json")
-		.query(&[("cftc_contract_market_code", CFTC_CODE_BTC), ("$order", "report_date_as_yyyy_mm_dd DESC"), ("$limit", "1")])
-		.send()
+// IPs; the Socrata data API serves the same reports as JSON with no challenge.
+pub async fn fetch_cftc_positions(params: &CftcParams) -> Result<CftcReport> {
+	fetch_reports(params, 1)
 		.await?
-		.json()
-		.await?;
-	let row = rows.into_iter().next().ok_or_else(|| eyre!("CFTC API returned no rows for BTC ({CFTC_CODE_BTC})"))?;
-	row.try_into()
+		.into_iter()
+		.next()
+		.ok_or_else(|| eyre!("CFTC API returned no {} rows for contract {}", params.kind.slug(), params.code))
 }
-static CFTC_CODE_BTC: &str = "133741";
 
-/// One "Traders in Financial Futures - Futures Only" report row, as served by the CFTC Socrata API
-/// (dataset gpe5-46if). Every numeric column arrives as a JSON string; spread-trader counts are
+/// The last `weeks` reports, oldest first.
+pub async fn fetch_cftc_history(params: &CftcParams, weeks: u32) -> Result<Vec<CftcReport>> {
+	let mut reports = fetch_reports(params, weeks).await?;
+	if reports.is_empty() {
+		return Err(eyre!("CFTC API returned no {} rows for contract {}", params.kind.slug(), params.code));
+	}
+	reports.sort_by_key(|r| r.date);
+	Ok(reports)
+}
+
+/// Newest first.
+async fn fetch_reports(params: &CftcParams, limit: u32) -> Result<Vec<CftcReport>> {
+	let dataset = params.kind.dataset();
+	let key = match limit {
+		1 => format!("cftc/{dataset}/{}", params.code),
+		n => format!("cftc/{dataset}/{}-{n}", params.code),
+	};
+	let limit = limit.to_string();
+	let body = cassette::http(&key, async {
+		Ok(reqwest::Client::new()
+			.get(format!("https://publicreporting.cftc.gov/resource/{dataset}.json"))
+			.query(&[
+				("cftc_contract_market_code", params.code.as_str()),
+				("$order", "report_date_as_yyyy_mm_dd DESC"),
+				("$limit", limit.as_str()),
+			])
+			.send()
+			.await?
+			.error_for_status()?
+			.text()
+			.await?)
+	})
+	.await?;
+	match params.kind {
+		ReportKind::TffFutures | ReportKind::TffCombined => parse::<TffRow>(&body, params.kind),
+		ReportKind::LegacyFutures | ReportKind::LegacyCombined => parse::<LegacyRow>(&body, params.kind),
+		ReportKind::DisaggregatedFutures => parse::<DisaggregatedRow>(&body, params.kind),
+	}
+}
+const CFTC_CODE_BTC: &str = "133741";
+
+/// A report row as one of the CFTC Socrata datasets serves it. Futures-only and combined variants of a
+/// report share their columns.
+trait Row: DeserializeOwned {
+	fn date(&self) -> &str;
+	/// In the order of [`ReportKind::categories`].
+	fn categories(self) -> Vec<(&'static str, Positions)>;
+}
+
+fn parse<R: Row>(body: &str, kind: ReportKind) -> Result<Vec<CftcReport>> {
+	let rows: Vec<R> = serde_json::from_str(body)?;
+	rows.into_iter()
+		.map(|r| {
+			let date = report_date(r.date())?;
+			Ok(CftcReport {
+				date,
+				kind,
+				categories: r.categories().into_iter().map(|(name, p)| (name.to_owned(), p)).collect(),
+			})
+		})
+		.collect()
+}
+
+/// Reports are as of Tuesday's close, released at 15:30 Eastern.
+fn report_date(raw: &str) -> Result<Timestamp> {
+	let day = raw.split('T').next().unwrap_or(raw);
+	let naive_date = Date::strptime("%Y-%m-%d", day).map_err(|e| eyre!("Failed to parse date `{day}`: {e}"))?;
+	let eastern = TimeZone::get("America/New_York").map_err(|e| eyre!("Failed to load Eastern timezone: {e}"))?;
+	Ok(naive_date
+		.at(15, 30, 0, 0)
+		.to_zoned(eastern)
+		.map_err(|e| eyre!("Failed to create Eastern timezone datetime: {e}"))?
+		.timestamp())
+}
+
+/// One "Traders in Financial Futures" report row, as served by the CFTC Socrata API (datasets
+/// gpe5-46if futures-only, yw9f-hn96 combined). Every numeric column arrives as a JSON string; spread-trader counts are
 /// omitted for all categories but leveraged funds.
 #[serde_as]
 #[derive(Deserialize)]
@@ -221,107 +381,469 @@ struct TffRow {
 	traders_other_rept_short: Option<u32>,
 }
 
-#[allow(unused)]
-#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, derive_new::new)]
-struct PositionsInfo {
-	current: f64,
-	change_since_last_week: f64,
-	percent_of_open: f64,
-	number_of_traders: Option<u32>,
+impl Row for TffRow {
+	fn date(&self) -> &str {
+		&self.report_date_as_yyyy_mm_dd
+	}
+
+	fn categories(self) -> Vec<(&'static str, Positions)> {
+		let r = self;
+		vec![
+			(
+				DEALER,
+				Positions::new(
+					PositionsInfo::new(r.dealer_positions_long_all, r.change_in_dealer_long_all, r.pct_of_oi_dealer_long_all, r.traders_dealer_long_all),
+					PositionsInfo::new(
+						r.dealer_positions_short_all,
+						r.change_in_dealer_short_all,
+						r.pct_of_oi_dealer_short_all,
+						r.traders_dealer_short_all,
+					),
+					Some(PositionsInfo::new(
+						r.dealer_positions_spread_all,
+						r.change_in_dealer_spread_all,
+						r.pct_of_oi_dealer_spread_all,
+						None,
+					)),
+				),
+			),
+			(
+				ASSET_MANAGER,
+				Positions::new(
+					PositionsInfo::new(r.asset_mgr_positions_long, r.change_in_asset_mgr_long, r.pct_of_oi_asset_mgr_long, r.traders_asset_mgr_long_all),
+					PositionsInfo::new(
+						r.asset_mgr_positions_short,
+						r.change_in_asset_mgr_short,
+						r.pct_of_oi_asset_mgr_short,
+						r.traders_asset_mgr_short_all,
+					),
+					Some(PositionsInfo::new(r.asset_mgr_positions_spread, r.change_in_asset_mgr_spread, r.pct_of_oi_asset_mgr_spread, None)),
+				),
+			),
+			(
+				LEVERAGED_FUNDS,
+				Positions::new(
+					PositionsInfo::new(r.lev_money_positions_long, r.change_in_lev_money_long, r.pct_of_oi_lev_money_long, r.traders_lev_money_long_all),
+					PositionsInfo::new(
+						r.lev_money_positions_short,
+						r.change_in_lev_money_short,
+						r.pct_of_oi_lev_money_short,
+						r.traders_lev_money_short_all,
+					),
+					Some(PositionsInfo::new(
+						r.lev_money_positions_spread,
+						r.change_in_lev_money_spread,
+						r.pct_of_oi_lev_money_spread,
+						r.traders_lev_money_spread,
+					)),
+				),
+			),
+			(
+				OTHER_REPORTABLES,
+				Positions::new(
+					PositionsInfo::new(
+						r.other_rept_positions_long,
+						r.change_in_other_rept_long,
+						r.pct_of_oi_other_rept_long,
+						r.traders_other_rept_long_all,
+					),
+					PositionsInfo::new(
+						r.other_rept_positions_short,
+						r.change_in_other_rept_short,
+						r.pct_of_oi_other_rept_short,
+						r.traders_other_rept_short,
+					),
+					Some(PositionsInfo::new(
+						r.other_rept_positions_spread,
+						r.change_in_other_rept_spread,
+						r.pct_of_oi_other_rept_spread,
+						None,
+					)),
+				),
+			),
+		]
+	}
 }
-impl TryFrom<TffRow> for CftcReport {
-	type Error = color_eyre::eyre::Report;
-
-	fn try_from(r: TffRow) -> Result<Self> {
-		let day = r.report_date_as_yyyy_mm_dd.split('T').next().unwrap_or(&r.report_date_as_yyyy_mm_dd);
-		let naive_date = Date::strptime("%Y-%m-%d", day).map_err(|e| eyre!("Failed to parse date `{day}`: {e}"))?;
-		let eastern = TimeZone::get("America/New_York").map_err(|e| eyre!("Failed to load Eastern timezone: {e}"))?;
-		let date = naive_date
-			.at(15, 30, 0, 0)
-			.to_zoned(eastern)
-			.map_err(|e| eyre!("Failed to create Eastern timezone datetime: {e}"))?
-			.timestamp();
-
-		Ok(CftcReport {
-			date,
-			dealer_intermidiary: Positions::new(
-				PositionsInfo::new(r.dealer_positions_long_all, r.change_in_dealer_long_all, r.pct_of_oi_dealer_long_all, r.traders_dealer_long_all),
-				PositionsInfo::new(
-					r.dealer_positions_short_all,
-					r.change_in_dealer_short_all,
-					r.pct_of_oi_dealer_short_all,
-					r.traders_dealer_short_all,
+
+/// One Legacy "Commitments of Traders" row (datasets 6dca-aqww futures-only, jun7-fc8e combined):
+/// speculators vs hedgers, plus the positions too small to report. Two of the noncommercial spread
+/// columns are misspelled upstream; the aliases accept both spellings in case it's ever fixed.
+#[serde_as]
+#[derive(Deserialize)]
+struct LegacyRow {
+	report_date_as_yyyy_mm_dd: String,
+
+	#[serde_as(as = "DisplayFromStr")]
+	noncomm_positions_long_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	noncomm_positions_short_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "noncomm_postions_spread_all")]
+	noncomm_positions_spread_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	comm_positions_long_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	comm_positions_short_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	nonrept_positions_long_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	nonrept_positions_short_all: f64,
+
+	#[serde_as(as = "DisplayFromStr")]
+	change_in_noncomm_long_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	change_in_noncomm_short_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "change_in_noncomm_spead_all")]
+	change_in_noncomm_spread_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	change_in_comm_long_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	change_in_comm_short_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	change_in_nonrept_long_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	change_in_nonrept_short_all: f64,
+
+	#[serde_as(as = "DisplayFromStr")]
+	pct_of_oi_noncomm_long_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	pct_of_oi_noncomm_short_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "pct_of_oi_noncomm_spread_all")]
+	pct_of_oi_noncomm_spread: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	pct_of_oi_comm_long_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	pct_of_oi_comm_short_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	pct_of_oi_nonrept_long_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	pct_of_oi_nonrept_short_all: f64,
+
+	#[serde_as(as = "Option<DisplayFromStr>")]
+	#[serde(default)]
+	traders_noncomm_long_all: Option<u32>,
+	#[serde_as(as = "Option<DisplayFromStr>")]
+	#[serde(default)]
+	traders_noncomm_short_all: Option<u32>,
+	#[serde_as(as = "Option<DisplayFromStr>")]
+	#[serde(default)]
+	traders_noncomm_spread_all: Option<u32>,
+	#[serde_as(as = "Option<DisplayFromStr>")]
+	#[serde(default)]
+	traders_comm_long_all: Option<u32>,
+	#[serde_as(as = "Option<DisplayFromStr>")]
+	#[serde(default)]
+	traders_comm_short_all: Option<u32>,
+}
+impl Row for LegacyRow {
+	fn date(&self) -> &str {
+		&self.report_date_as_yyyy_mm_dd
+	}
+
+	fn categories(self) -> Vec<(&'static str, Positions)> {
+		let r = self;
+		vec![
+			(
+				NONCOMMERCIAL,
+				Positions::new(
+					PositionsInfo::new(
+						r.noncomm_positions_long_all,
+						r.change_in_noncomm_long_all,
+						r.pct_of_oi_noncomm_long_all,
+						r.traders_noncomm_long_all,
+					),
+					PositionsInfo::new(
+						r.noncomm_positions_short_all,
+						r.change_in_noncomm_short_all,
+						r.pct_of_oi_noncomm_short_all,
+						r.traders_noncomm_short_all,
+					),
+					Some(PositionsInfo::new(
+						r.noncomm_positions_spread_all,
+						r.change_in_noncomm_spread_all,
+						r.pct_of_oi_noncomm_spread,
+						r.traders_noncomm_spread_all,
+					)),
 				),
-				PositionsInfo::new(r.dealer_positions_spread_all, r.change_in_dealer_spread_all, r.pct_of_oi_dealer_spread_all, None),
 			),
-			asset_manager_or_institutional: Positions::new(
-				PositionsInfo::new(r.asset_mgr_positions_long, r.change_in_asset_mgr_long, r.pct_of_oi_asset_mgr_long, r.traders_asset_mgr_long_all),
-				PositionsInfo::new(
-					r.asset_mgr_positions_short,
-					r.change_in_asset_mgr_short,
-					r.pct_of_oi_asset_mgr_short,
-					r.traders_asset_mgr_short_all,
+			(
+				COMMERCIAL,
+				Positions::new(
+					PositionsInfo::new(r.comm_positions_long_all, r.change_in_comm_long_all, r.pct_of_oi_comm_long_all, r.traders_comm_long_all),
+					PositionsInfo::new(r.comm_positions_short_all, r.change_in_comm_short_all, r.pct_of_oi_comm_short_all, r.traders_comm_short_all),
+					None,
 				),
-				PositionsInfo::new(r.asset_mgr_positions_spread, r.change_in_asset_mgr_spread, r.pct_of_oi_asset_mgr_spread, None),
 			),
-			leveraged_funds: Positions::new(
-				PositionsInfo::new(r.lev_money_positions_long, r.change_in_lev_money_long, r.pct_of_oi_lev_money_long, r.traders_lev_money_long_all),
-				PositionsInfo::new(
-					r.lev_money_positions_short,
-					r.change_in_lev_money_short,
-					r.pct_of_oi_lev_money_short,
-					r.traders_lev_money_short_all,
+			(
+				NONREPORTABLE,
+				Positions::new(
+					PositionsInfo::new(r.nonrept_positions_long_all, r.change_in_nonrept_long_all, r.pct_of_oi_nonrept_long_all, None),
+					PositionsInfo::new(r.nonrept_positions_short_all, r.change_in_nonrept_short_all, r.pct_of_oi_nonrept_short_all, None),
+					None,
 				),
-				PositionsInfo::new(
-					r.lev_money_positions_spread,
-					r.change_in_lev_money_spread,
-					r.pct_of_oi_lev_money_spread,
-					r.traders_lev_money_spread,
+			),
+		]
+	}
+}
+
+/// One "Disaggregated Commitments of Traders - Futures Only" row (dataset 72hh-3qpy). The CFTC only
+/// publishes it for physical commodities, so financial contracts come back empty. Column names aren't
+/// consistent about the `_all` suffix (and double the underscore in two swap columns); aliases take either.
+#[serde_as]
+#[derive(Deserialize)]
+struct DisaggregatedRow {
+	report_date_as_yyyy_mm_dd: String,
+
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "prod_merc_positions_long_all")]
+	prod_merc_positions_long: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "prod_merc_positions_short_all")]
+	prod_merc_positions_short: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	swap_positions_long_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "swap__positions_short_all")]
+	swap_positions_short_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "swap__positions_spread_all")]
+	swap_positions_spread_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	m_money_positions_long_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	m_money_positions_short_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "m_money_positions_spread_all")]
+	m_money_positions_spread: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "other_rept_positions_long_all")]
+	other_rept_positions_long: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "other_rept_positions_short_all")]
+	other_rept_positions_short: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "other_rept_positions_spread_all")]
+	other_rept_positions_spread: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	nonrept_positions_long_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	nonrept_positions_short_all: f64,
+
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "change_in_prod_merc_long_all")]
+	change_in_prod_merc_long: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "change_in_prod_merc_short_all")]
+	change_in_prod_merc_short: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	change_in_swap_long_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	change_in_swap_short_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	change_in_swap_spread_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	change_in_m_money_long_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	change_in_m_money_short_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "change_in_m_money_spread_all")]
+	change_in_m_money_spread: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "change_in_other_rept_long_all")]
+	change_in_other_rept_long: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "change_in_other_rept_short_all")]
+	change_in_other_rept_short: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "change_in_other_rept_spread_all")]
+	change_in_other_rept_spread: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	change_in_nonrept_long_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	change_in_nonrept_short_all: f64,
+
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "pct_of_oi_prod_merc_long_all")]
+	pct_of_oi_prod_merc_long: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "pct_of_oi_prod_merc_short_all")]
+	pct_of_oi_prod_merc_short: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	pct_of_oi_swap_long_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	pct_of_oi_swap_short_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	pct_of_oi_swap_spread_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	pct_of_oi_m_money_long_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	pct_of_oi_m_money_short_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "pct_of_oi_m_money_spread_all")]
+	pct_of_oi_m_money_spread: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "pct_of_oi_other_rept_long_all")]
+	pct_of_oi_other_rept_long: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "pct_of_oi_other_rept_short_all")]
+	pct_of_oi_other_rept_short: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	#[serde(alias = "pct_of_oi_other_rept_spread_all")]
+	pct_of_oi_other_rept_spread: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	pct_of_oi_nonrept_long_all: f64,
+	#[serde_as(as = "DisplayFromStr")]
+	pct_of_oi_nonrept_short_all: f64,
+
+	#[serde_as(as = "Option<DisplayFromStr>")]
+	#[serde(default)]
+	traders_prod_merc_long_all: Option<u32>,
+	#[serde_as(as = "Option<DisplayFromStr>")]
+	#[serde(default)]
+	traders_prod_merc_short_all: Option<u32>,
+	#[serde_as(as = "Option<DisplayFromStr>")]
+	#[serde(default)]
+	traders_swap_long_all: Option<u32>,
+	#[serde_as(as = "Option<DisplayFromStr>")]
+	#[serde(default)]
+	traders_swap_short_all: Option<u32>,
+	#[serde_as(as = "Option<DisplayFromStr>")]
+	#[serde(default)]
+	traders_swap_spread_all: Option<u32>,
+	#[serde_as(as = "Option<DisplayFromStr>")]
+	#[serde(default)]
+	traders_m_money_long_all: Option<u32>,
+	#[serde_as(as = "Option<DisplayFromStr>")]
+	#[serde(default)]
+	traders_m_money_short_all: Option<u32>,
+	#[serde_as(as = "Option<DisplayFromStr>")]
+	#[serde(default)]
+	traders_m_money_spread_all: Option<u32>,
+	#[serde_as(as = "Option<DisplayFromStr>")]
+	#[serde(default)]
+	traders_other_rept_long_all: Option<u32>,
+	#[serde_as(as = "Option<DisplayFromStr>")]
+	#[serde(default, alias = "traders_other_rept_short_all")]
+	traders_other_rept_short: Option<u32>,
+	#[serde_as(as = "Option<DisplayFromStr>")]
+	#[serde(default, alias = "traders_other_rept_spread_all")]
+	traders_other_rept_spread: Option<u32>,
+}
+impl Row for DisaggregatedRow {
+	fn date(&self) -> &str {
+		&self.report_date_as_yyyy_mm_dd
+	}
+
+	fn categories(self) -> Vec<(&'static str, Positions)> {
+		let r = self;
+		vec![
+			(
+				PRODUCER_MERCHANT,
+				Positions::new(
+					PositionsInfo::new(r.prod_merc_positions_long, r.change_in_prod_merc_long, r.pct_of_oi_prod_merc_long, r.traders_prod_merc_long_all),
+					PositionsInfo::new(
+						r.prod_merc_positions_short,
+						r.change_in_prod_merc_short,
+						r.pct_of_oi_prod_merc_short,
+						r.traders_prod_merc_short_all,
+					),
+					None,
 				),
 			),
-			other_reportables: Positions::new(
-				PositionsInfo::new(
-					r.other_rept_positions_long,
-					r.change_in_other_rept_long,
-					r.pct_of_oi_other_rept_long,
-					r.traders_other_rept_long_all,
+			(
+				SWAP_DEALERS,
+				Positions::new(
+					PositionsInfo::new(r.swap_positions_long_all, r.change_in_swap_long_all, r.pct_of_oi_swap_long_all, r.traders_swap_long_all),
+					PositionsInfo::new(r.swap_positions_short_all, r.change_in_swap_short_all, r.pct_of_oi_swap_short_all, r.traders_swap_short_all),
+					Some(PositionsInfo::new(
+						r.swap_positions_spread_all,
+						r.change_in_swap_spread_all,
+						r.pct_of_oi_swap_spread_all,
+						r.traders_swap_spread_all,
+					)),
 				),
-				PositionsInfo::new(
-					r.other_rept_positions_short,
-					r.change_in_other_rept_short,
-					r.pct_of_oi_other_rept_short,
-					r.traders_other_rept_short,
+			),
+			(
+				MANAGED_MONEY,
+				Positions::new(
+					PositionsInfo::new(
+						r.m_money_positions_long_all,
+						r.change_in_m_money_long_all,
+						r.pct_of_oi_m_money_long_all,
+						r.traders_m_money_long_all,
+					),
+					PositionsInfo::new(
+						r.m_money_positions_short_all,
+						r.change_in_m_money_short_all,
+						r.pct_of_oi_m_money_short_all,
+						r.traders_m_money_short_all,
+					),
+					Some(PositionsInfo::new(
+						r.m_money_positions_spread,
+						r.change_in_m_money_spread,
+						r.pct_of_oi_m_money_spread,
+						r.traders_m_money_spread_all,
+					)),
 				),
-				PositionsInfo::new(r.other_rept_positions_spread, r.change_in_other_rept_spread, r.pct_of_oi_other_rept_spread, None),
 			),
-		})
+			(
+				OTHER_REPORTABLES,
+				Positions::new(
+					PositionsInfo::new(
+						r.other_rept_positions_long,
+						r.change_in_other_rept_long,
+						r.pct_of_oi_other_rept_long,
+						r.traders_other_rept_long_all,
+					),
+					PositionsInfo::new(
+						r.other_rept_positions_short,
+						r.change_in_other_rept_short,
+						r.pct_of_oi_other_rept_short,
+						r.traders_other_rept_short,
+					),
+					Some(PositionsInfo::new(
+						r.other_rept_positions_spread,
+						r.change_in_other_rept_spread,
+						r.pct_of_oi_other_rept_spread,
+						r.traders_other_rept_spread,
+					)),
+				),
+			),
+			(
+				NONREPORTABLE,
+				Positions::new(
+					PositionsInfo::new(r.nonrept_positions_long_all, r.change_in_nonrept_long_all, r.pct_of_oi_nonrept_long_all, None),
+					PositionsInfo::new(r.nonrept_positions_short_all, r.change_in_nonrept_short_all, r.pct_of_oi_nonrept_short_all, None),
+					None,
+				),
+			),
+		]
 	}
 }
+
+#[allow(unused)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, derive_new::new)]
+struct PositionsInfo {
+	current: f64,
+	change_since_last_week: f64,
+	percent_of_open: f64,
+	number_of_traders: Option<u32>,
+}
 impl std::fmt::Display for CftcReport {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		let institutional_change = {
-			let institutional = &self.asset_manager_or_institutional;
-			DirectionalPositionsChange {
-				name: "Institutional",
-				long: NowThen::from_now_diff(institutional.long.current, institutional.long.change_since_last_week),
-				short: NowThen::from_now_diff(institutional.short.current, institutional.short.change_since_last_week),
-			}
-		};
-		let funds_change = {
-			let funds = &self.leveraged_funds;
-			DirectionalPositionsChange {
-				name: "Hedgefunds",
-				long: NowThen::from_now_diff(funds.long.current, funds.long.change_since_last_week),
-				short: NowThen::from_now_diff(funds.short.current, funds.short.change_since_last_week),
-			}
-		};
-		write!(
-			f,
-			"CFTC Report as of {}:\n- {}\n- {}",
-			self.date,
-			institutional_change.to_string_pretty(2),
-			funds_change.to_string_pretty(2)
-		)
+		write!(f, "CFTC {} as of {}:", self.kind.label(), self.date)?;
+		for name in self.kind.headline() {
+			let Some((_, p)) = self.categories.iter().find(|(n, _)| n == name) else { continue };
+			let change = DirectionalPositionsChange {
+				name,
+				long: NowThen::from_now_diff(p.long.current, p.long.change_since_last_week),
+				short: NowThen::from_now_diff(p.short.current, p.short.change_since_last_week),
+			};
+			write!(f, "\n- {}", change.to_string_pretty(2))?;
+		}
+		Ok(())
 	}
 }
 
@@ -338,3 +860,72 @@ impl PrettyPrint for DirectionalPositionsChange {
 		write!(f, "{indent}Long: {}, Short: {}", self.long, self.short)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn btc(kind: ReportKind) -> CftcParams {
+		CftcParams {
+			code: CFTC_CODE_BTC.to_owned(),
+			kind,
+		}
+	}
+
+	#[tokio::test]
+	async fn tff_row_to_report() {
+		let report = cassette::replay(fetch_cftc_positions(&btc(ReportKind::TffFutures))).await.unwrap();
+
+		// Tuesday 2025-10-07, 15:30 EDT
+		assert_eq!(report.date, Timestamp::from_second(1_759_865_400).unwrap());
+		assert_eq!(report.kind, ReportKind::TffFutures);
+		let names: Vec<&str> = report.categories.iter().map(|(n, _)| n.as_str()).collect();
+		assert_eq!(names, [DEALER, ASSET_MANAGER, LEVERAGED_FUNDS, OTHER_REPORTABLES]);
+
+		let (_, lev) = &report.categories[2];
+		assert_eq!((lev.long.current, lev.short.current), (6_120.0, 17_245.0));
+		assert_eq!((lev.long.change_since_last_week, lev.short.change_since_last_week), (-380.0, 655.0));
+		assert_eq!((lev.long.percent_of_open, lev.short.percent_of_open), (22.6, 63.7));
+		assert_eq!((lev.long.number_of_traders, lev.short.number_of_traders), (Some(31), Some(44)));
+		let spread = lev.spreading.expect("TFF breaks spreading out for every category");
+		assert_eq!((spread.current, spread.number_of_traders), (912.0, Some(11)));
+		assert_eq!(lev.net(), -11_125.0);
+
+		// spread-trader counts are only published for leveraged funds
+		let (_, dealer) = &report.categories[0];
+		assert_eq!(dealer.spreading.unwrap().number_of_traders, None);
+		assert_eq!(dealer.long.number_of_traders, Some(9));
+	}
+
+	#[tokio::test]
+	async fn tff_history_is_oldest_first() {
+		let reports = cassette::replay(fetch_cftc_history(&btc(ReportKind::TffFutures), 2)).await.unwrap();
+		let dates: Vec<i64> = reports.iter().map(|r| r.date.as_second()).collect();
+		assert_eq!(dates, [1_759_260_600, 1_759_865_400]);
+		assert_eq!(reports[0].categories[1].1.long.current, 8_622.0);
+	}
+
+	#[tokio::test]
+	async fn legacy_row_to_report() {
+		let report = cassette::replay(fetch_cftc_positions(&btc(ReportKind::LegacyFutures))).await.unwrap();
+
+		assert_eq!(report.date, Timestamp::from_second(1_759_865_400).unwrap());
+		let names: Vec<&str> = report.categories.iter().map(|(n, _)| n.as_str()).collect();
+		assert_eq!(names, [NONCOMMERCIAL, COMMERCIAL, NONREPORTABLE]);
+
+		// upstream spells two of these columns `postions` and `spead`
+		let (_, noncomm) = &report.categories[0];
+		let spread = noncomm.spreading.expect("noncommercials have a spreading column");
+		assert_eq!((spread.current, spread.change_since_last_week, spread.percent_of_open), (1_412.0, 38.0, 5.2));
+		assert_eq!(spread.number_of_traders, Some(18));
+		assert_eq!(noncomm.net(), -2_332.0);
+
+		let (_, comm) = &report.categories[1];
+		assert!(comm.spreading.is_none());
+		assert_eq!((comm.long.current, comm.short.current, comm.short.number_of_traders), (6_218.0, 4_107.0, Some(19)));
+
+		// nonreportable positions have no trader count
+		let (_, nonrept) = &report.categories[2];
+		assert_eq!((nonrept.long.current, nonrept.long.number_of_traders), (1_652.0, None));
+	}
+}
@@ -4,10 +4,77 @@ use leptos::{html::*, prelude::*};
 
 use super::{LoadingIndicator, LoadingIndicatorProps};
 
+/// Weekly reports the history chart spans; about a year.
+#[cfg_attr(not(feature = "ssr"), allow(dead_code))]
+const HISTORY_WEEKS: u32 = 52;
+
+/// Which COT report a panel shows. Each is its own Socrata dataset with its own trader categories.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum ReportKind {
+	/// Traders in Financial Futures, futures only.
+	#[default]
+	TffFutures,
+	/// Traders in Financial Futures, futures and options combined.
+	TffCombined,
+	/// Legacy COT: commercials vs non-commercials, futures only.
+	LegacyFutures,
+	LegacyCombined,
+	/// Disaggregated COT, futures only. Only offered for contracts flagged
+	/// [`physical`](crate::config::CftcContract::physical): the CFTC doesn't publish it for financial ones.
+	DisaggregatedFutures,
+}
+impl ReportKind {
+	pub const ALL: [Self; 5] = [Self::TffFutures, Self::TffCombined, Self::LegacyFutures, Self::LegacyCombined, Self::DisaggregatedFutures];
+
+	pub fn label(self) -> &'static str {
+		match self {
+			Self::TffFutures => "TFF",
+			Self::TffCombined => "TFF + options",
+			Self::LegacyFutures => "Legacy",
+			Self::LegacyCombined => "Legacy + options",
+			Self::DisaggregatedFutures => "Disaggregated",
+		}
+	}
+
+	pub fn slug(self) -> &'static str {
+		match self {
+			Self::TffFutures => "tff",
+			Self::TffCombined => "tff-combined",
+			Self::LegacyFutures => "legacy",
+			Self::LegacyCombined => "legacy-combined",
+			Self::DisaggregatedFutures => "disaggregated",
+		}
+	}
+
+	pub fn from_slug(s: &str) -> Option<Self> {
+		Self::ALL.into_iter().find(|k| k.slug() == s)
+	}
+
+	/// Whether the CFTC publishes this report for a contract, given whether it's a physical commodity.
+	pub fn covers(self, physical: bool) -> bool {
+		physical || self != Self::DisaggregatedFutures
+	}
+}
+
 #[component]
 pub fn CftcReportView() -> impl IntoView {
 	let trigger = RwSignal::new(());
-	let report_resource = Resource::new(move || trigger.get(), |_| async move { try_build().await });
+	// `None` until the reader switches: the server opens on the first configured contract
+	let code = RwSignal::new(None::<String>);
+	let kind = RwSignal::new(ReportKind::default());
+	let contracts = Resource::new(|| (), |_| cftc_contracts());
+	let report_resource = Resource::new(move || (code.get(), kind.get(), trigger.get()), |(code, kind, _)| try_build(code, kind));
+
+	// Refetch the moment the server persists a fresh copy of any contract's report
+	#[cfg(feature = "hydrate")]
+	super::on_source_event_where(
+		|s| s.starts_with("CftcReport-"),
+		move |ev| {
+			if let super::SourceEvent::Refreshed { .. } = ev {
+				trigger.update(|_| ());
+			}
+		},
+	);
 
 	// Set up retry interval - retry every 1 minute on error
 	#[cfg(not(feature = "ssr"))]
@@ -24,25 +91,91 @@ pub fn CftcReportView() -> impl IntoView {
 		});
 	}
 
-	div().class("panel-center").child(Suspense(SuspenseProps {
-		fallback: { || LoadingIndicator(LoadingIndicatorProps { label: "CFTC Report".into() }) }.into(),
-		#[rustfmt::skip]
-		children: ToChildren::to_children(move || IntoRender::into_render(move || match report_resource.get() {
-			Some(Ok(report_data)) => (pre().child(report_data.short),).into_any(),
-			Some(Err(e)) => (pre().child(format!("Error loading CFTC Report: {e} (retrying...)")),).into_any(),
-			None => (LoadingIndicator(LoadingIndicatorProps { label: "CFTC Report".into() }),).into_any(),
-		})),
-	}))
+	let switcher = move || {
+		let Some(Ok(contracts)) = contracts.get() else { return None };
+		(contracts.len() > 1).then(|| {
+			select()
+				.class("mb-2 p-1 border rounded text-sm")
+				.on(leptos::ev::change, move |ev| code.set(Some(event_target_value(&ev))))
+				.child(
+					contracts
+						.into_iter()
+						.enumerate()
+						.map(|(i, c)| option().value(c.code.clone()).selected(code.get().map_or(i == 0, |sel| sel == c.code)).child(c.name))
+						.collect::<Vec<_>>(),
+				)
+		})
+	};
+	// whether the contract the switcher shows as selected is a physical commodity; false until the list loads
+	let physical = move || {
+		let Some(Ok(contracts)) = contracts.get() else { return false };
+		let selected = code.get();
+		contracts
+			.into_iter()
+			.enumerate()
+			.find(|(i, c)| selected.as_ref().map_or(*i == 0, |sel| *sel == c.code))
+			.is_some_and(|(_, c)| c.physical)
+	};
+	// switching to a contract the current report isn't published for falls back to the default one
+	Effect::new(move || {
+		if !kind.get_untracked().covers(physical()) {
+			kind.set(ReportKind::default());
+		}
+	});
+	let kind_switcher = move || {
+		let physical = physical();
+		select()
+			.class("mb-2 ml-2 p-1 border rounded text-sm")
+			.on(leptos::ev::change, move |ev| {
+				if let Some(k) = ReportKind::from_slug(&event_target_value(&ev)) {
+					kind.set(k);
+				}
+			})
+			.child(
+				ReportKind::ALL
+					.into_iter()
+					.filter(|k| k.covers(physical))
+					.map(|k| option().value(k.slug()).selected(move || kind.get() == k).child(k.label()))
+					.collect::<Vec<_>>(),
+			)
+	};
+
+	div().class("panel-center").child(div().class("w-full max-h-full overflow-auto").child((
+		div().child((switcher, kind_switcher)),
+		Suspense(SuspenseProps {
+			fallback: { || LoadingIndicator(LoadingIndicatorProps { label: "CFTC Report".into() }) }.into(),
+			#[rustfmt::skip]
+			children: ToChildren::to_children(move || IntoRender::into_render(move || match report_resource.get() {
+				Some(Ok(report_data)) => div().child((
+					pre().child(report_data.short),
+					details().class("mt-2 text-left").child((
+						summary().class("cursor-pointer text-center").child("Full report table"),
+						div().class("cftc-table text-xs overflow-x-auto").inner_html(report_data.table_html),
+					)),
+					CftcHistoryChart(CftcHistoryChartProps { code, kind }),
+				)).into_any(),
+				Some(Err(e)) => (pre().child(format!("Error loading CFTC Report: {e} (retrying...)")),).into_any(),
+				None => (LoadingIndicator(LoadingIndicatorProps { label: "CFTC Report".into() }),).into_any(),
+			})),
+		}),
+	)))
 }
 
 #[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize, derive_new::new)]
 pub struct CftcReportRendered {
 	short: String,
-	markdown: String,
+	/// [`to_markdown_table`](data::CftcReport::to_markdown_table), rendered to HTML server-side.
+	table_html: String,
+}
+/// The contracts the switcher offers, from `Settings`.
+#[server]
+async fn cftc_contracts() -> Result<Vec<crate::config::CftcContract>, ServerFnError> {
+	Ok(crate::app::server_impl::get_settings()?.cftc.contracts)
 }
+
 #[server]
-async fn try_build() -> Result<CftcReportRendered, ServerFnError> {
-	let report = super::_core::load::<data::CftcReport>()
+async fn try_build(code: Option<String>, kind: ReportKind) -> Result<CftcReportRendered, ServerFnError> {
+	let report = super::_core::load_with::<data::CftcReport>(&params(code, kind)?)
 		.await
 		.map_err(|e| {
 			tracing::error!("Failed to load CFTC positions: {e:?}");
@@ -51,19 +184,239 @@ async fn try_build() -> Result<CftcReportRendered, ServerFnError> {
 		.data;
 	Ok(report.into())
 }
+
+/// Net contracts (long − short) per trader category, one point per weekly report, oldest first.
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct CftcHistory {
+	time: Vec<i64>, // UNIX seconds, report date
+	series: Vec<NetSeries>,
+}
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+struct NetSeries {
+	label: String,
+	color: String,
+	net: Vec<f64>,
+}
+
+#[component]
+fn CftcHistoryChart(code: RwSignal<Option<String>>, kind: RwSignal<ReportKind>) -> impl IntoView {
+	let trigger = RwSignal::new(());
+	let history = Resource::new(move || (code.get(), kind.get(), trigger.get()), |(code, kind, _)| cftc_history(code, kind));
+
+	#[cfg(feature = "hydrate")]
+	super::on_source_event_where(
+		|s| s.starts_with("CftcHistory-"),
+		move |ev| {
+			if let super::SourceEvent::Refreshed { .. } = ev {
+				trigger.update(|_| ());
+			}
+		},
+	);
+
+	div().class("mt-2 w-full").child(Suspense(SuspenseProps {
+		fallback: (|| LoadingIndicator(LoadingIndicatorProps { label: "COT history".into() })).into(),
+		children: ToChildren::to_children(move || {
+			IntoRender::into_render(move || match history.get() {
+				Some(Ok(h)) => net_chart(&h).into_any(),
+				Some(Err(e)) => pre().class("text-xs").child(format!("No COT history: {e}")).into_any(),
+				None => ().into_any(),
+			})
+		}),
+	}))
+}
+
+/// Inline SVG of each category's net position on a shared contracts axis, with the zero line when it's in range.
+fn net_chart(h: &CftcHistory) -> impl IntoView {
+	const W: f64 = 300.0;
+	const H: f64 = 100.0;
+	let (t0, t1) = (h.time.first().copied().unwrap_or(0), h.time.last().copied().unwrap_or(0));
+	let (lo, hi) = h.series.iter().flat_map(|s| &s.net).fold((f64::MAX, f64::MIN), |(lo, hi), v| (lo.min(*v), hi.max(*v)));
+	let pad = ((hi - lo) * 0.05).max(1.0);
+	let (lo, hi) = (lo - pad, hi + pad);
+	let x = |t: i64| if t1 > t0 { (t - t0) as f64 / (t1 - t0) as f64 * W } else { W };
+	let y = |v: f64| (hi - v) / (hi - lo) * H;
+
+	let lines = h
+		.series
+		.iter()
+		.map(|s| {
+			let pts = h.time.iter().zip(&s.net).map(|(t, v)| format!("{:.1},{:.1}", x(*t), y(*v))).collect::<Vec<_>>().join(" ");
+			leptos::svg::polyline()
+				.attr("points", pts)
+				.attr("fill", "none")
+				.attr("stroke", s.color.clone())
+				.attr("stroke-width", "1.5")
+				.attr("vector-effect", "non-scaling-stroke")
+		})
+		.collect::<Vec<_>>();
+	let zero = (lo < 0.0 && 0.0 < hi).then(|| {
+		leptos::svg::line()
+			.attr("x1", "0")
+			.attr("x2", W.to_string())
+			.attr("y1", y(0.0).to_string())
+			.attr("y2", y(0.0).to_string())
+			.attr("stroke", "#94a3b8")
+			.attr("stroke-dasharray", "3 3")
+			.attr("vector-effect", "non-scaling-stroke")
+	});
+	let legend = h
+		.series
+		.iter()
+		.map(|s| {
+			let last = s.net.last().map(|v| format!("{v:+.0}")).unwrap_or_default();
+			span().child((span().style(format!("color: {}", s.color)).child("■ "), format!("{} {last}", s.label)))
+		})
+		.collect::<Vec<_>>();
+
+	div().child((
+		leptos::svg::svg()
+			.attr("viewBox", format!("0 0 {W} {H}"))
+			.attr("preserveAspectRatio", "none")
+			.class("w-full h-24")
+			.child((zero, lines)),
+		div()
+			.class("flex flex-wrap justify-center gap-x-3 text-xs")
+			.child((legend, span().class("text-gray-500").child(format!("net contracts, last {} reports", h.time.len())))),
+	))
+}
+
+#[server]
+async fn cftc_history(code: Option<String>, kind: ReportKind) -> Result<CftcHistory, ServerFnError> {
+	Ok(super::_core::load_with::<CftcHistory>(&params(code, kind)?)
+		.await
+		.map_err(|e| {
+			tracing::error!("Failed to load CFTC history: {e:?}");
+			ServerFnError::new(format!("Failed to load CFTC history: {e}"))
+		})?
+		.data)
+}
+
+/// `code`, or the first configured contract for `None`. Contracts not in `Settings` aren't served:
+/// they're all the switcher offers, and it keeps the cached variants (and upstream calls) bounded.
+#[cfg(feature = "ssr")]
+fn params(code: Option<String>, kind: ReportKind) -> Result<data::CftcParams, ServerFnError> {
+	let contracts = crate::app::server_impl::get_settings()?.cftc.contracts;
+	let contract = match code {
+		None => contracts.into_iter().next().ok_or_else(|| ServerFnError::new("No CFTC contracts configured"))?,
+		Some(code) => contracts
+			.into_iter()
+			.find(|c| c.code == code)
+			.ok_or_else(|| ServerFnError::new(format!("CFTC contract {code} is not configured")))?,
+	};
+	if !kind.covers(contract.physical) {
+		return Err(ServerFnError::new(format!(
+			"No {} report for {}: it only covers physical commodities",
+			kind.label(),
+			contract.name
+		)));
+	}
+	Ok(data::CftcParams { code: contract.code, kind })
+}
+
+/// Keeps every configured contract's default report warm; the list is read once here, so adding one
+/// takes a restart. Other report kinds are fetched when first viewed.
+#[cfg(feature = "ssr")]
+pub(super) fn register(scheduler: &mut super::_core::Scheduler, settings: &crate::config::LiveSettings) {
+	let contracts = settings.config().map(|s| s.cftc.contracts).unwrap_or_else(|_| crate::config::CftcConf::default().contracts);
+	for c in contracts {
+		let params = data::CftcParams {
+			code: c.code,
+			kind: ReportKind::default(),
+		};
+		scheduler.register_with::<data::CftcReport>(params.clone());
+		scheduler.register_with::<CftcHistory>(params);
+	}
+}
 #[cfg(feature = "ssr")]
 impl super::_core::SourceData for data::CftcReport {
+	type Params = data::CftcParams;
+
 	fn decay_horizon() -> v_utils::trades::Timeframe {
 		"6h".into() // report publishes weekly; a few polls/day catches the Friday release
 	}
 
-	async fn fetch() -> color_eyre::eyre::Result<Self> {
-		data::fetch_cftc_positions().await
+	async fn fetch(params: &data::CftcParams) -> color_eyre::eyre::Result<Self> {
+		data::fetch_cftc_positions(params).await
+	}
+
+	fn fixture(params: &data::CftcParams) -> Option<Self> {
+		Some(data::CftcReport::sample_for(params))
 	}
 }
 #[cfg(feature = "ssr")]
+impl super::_core::SourceData for CftcHistory {
+	type Params = data::CftcParams;
+
+	fn decay_horizon() -> v_utils::trades::Timeframe {
+		"6h".into() // same release as `CftcReport`
+	}
+
+	/// Already a year of reports per poll, and `CftcReport` keeps each week's as it comes.
+	fn history_retention() -> Option<jiff::SignedDuration> {
+		None
+	}
+
+	async fn fetch(params: &data::CftcParams) -> color_eyre::eyre::Result<Self> {
+		Ok(data::fetch_cftc_history(params, HISTORY_WEEKS).await?.as_slice().into())
+	}
+
+	/// The sample report's week, preceded by a year of weeks swinging around its levels.
+	fn fixture(params: &data::CftcParams) -> Option<Self> {
+		let sample = data::CftcReport::sample_for(params);
+		let n = HISTORY_WEEKS as i64;
+		let week = 7 * 24 * 3600;
+		let end = sample.date.as_second();
+		let time: Vec<i64> = (0..n).map(|i| end - (n - 1 - i) * week).collect();
+		let series = sample
+			.categories
+			.iter()
+			.enumerate()
+			.map(|(k, (label, positions))| NetSeries {
+				label: label.into(),
+				color: category_color(k).into(),
+				net: {
+					// a year-long swing, pinned so the last week is the sample's own figure
+					let swing = |i: i64| (std::f64::consts::TAU * (i as f64 / n as f64 + k as f64 * 0.21)).sin() * positions.net().abs().max(500.0) * 0.3;
+					(0..n).map(|i| positions.net() + swing(i) - swing(n - 1)).collect()
+				},
+			})
+			.collect();
+		Some(Self { time, series })
+	}
+}
+#[cfg(feature = "ssr")]
+impl From<&[data::CftcReport]> for CftcHistory {
+	fn from(reports: &[data::CftcReport]) -> Self {
+		// every report of a kind has the same categories, in the same order
+		let labels = reports
+			.first()
+			.map(|r| r.categories.iter().map(|(label, _)| label.clone()).collect::<Vec<_>>())
+			.unwrap_or_default();
+		Self {
+			time: reports.iter().map(|r| r.date.as_second()).collect(),
+			series: labels
+				.into_iter()
+				.enumerate()
+				.map(|(k, label)| NetSeries {
+					label,
+					color: category_color(k).into(),
+					net: reports.iter().map(|r| r.categories.get(k).map_or(0.0, |(_, p)| p.net())).collect(),
+				})
+				.collect(),
+		}
+	}
+}
+#[cfg_attr(not(feature = "ssr"), allow(dead_code))]
+fn category_color(k: usize) -> &'static str {
+	["#64748b", "#2563eb", "#dc2626", "#16a34a", "#d97706"][k % 5]
+}
+#[cfg(feature = "ssr")]
 impl From<data::CftcReport> for CftcReportRendered {
 	fn from(report: data::CftcReport) -> Self {
-		Self::new(report.to_string(), report.to_markdown_table())
+		use pulldown_cmark::{Options, Parser, html};
+
+		let mut table_html = String::new();
+		html::push_html(&mut table_html, Parser::new_ext(&report.to_markdown_table(), Options::ENABLE_TABLES));
+		Self::new(report.to_string(), table_html)
 	}
 }
@@ -1,4 +1,4 @@
-//! The dashboard rendered as a packed-grid dock: the five sub-dashboards become draggable/resizable
+//! The dashboard rendered as a packed-grid dock: the sub-dashboards become draggable/resizable
 //! panels. `s` saves the live arrangement; three saved layouts are kept, keyed by device band, so
 //! each screen class opens onto a sensible seed. This island is the whole dashboard now — its child
 //! views are plain components that hydrate within it.
@@ -10,11 +10,11 @@ use std::{cell::Cell, rc::Rc, sync::Arc};
 use dockviewers::leptos::{Breakpoint, Config, DockPanel, Group, Keybind, MinSize, PackedApi, PackedArea, PackedState, PanelId, Step};
 use leptos::prelude::*;
 
-use super::{cme, fng, lsr, market_structure, vol};
+use super::{cme, depth, fng, funding, liquidations, lsr, market_structure, vol};
 
 /// Every panel this deck hosts. A saved layout that doesn't cover all of these is treated as
 /// unusable on load (see `on_ready`) and replaced by the seed.
-const PANEL_IDS: [&str; 5] = ["market_structure", "lsr", "cme", "vol", "fng"];
+const PANEL_IDS: [&str; 9] = ["market_structure", "pair", "lsr", "cme", "vol", "fng", "funding", "liquidations", "depth"];
 #[island]
 pub fn DashboardDeck() -> impl IntoView {
 	let panels = RwSignal::new(vec![
@@ -23,6 +23,11 @@ pub fn DashboardDeck() -> impl IntoView {
 			title: "Market Structure".into(),
 			content: Arc::new(|| market_structure::MarketStructureView().into_any()),
 		},
+		DockPanel {
+			id: PanelId("pair".into()),
+			title: "Pair".into(),
+			content: Arc::new(|| market_structure::PairView().into_any()),
+		},
 		DockPanel {
 			id: PanelId("lsr".into()),
 			title: "LSR".into(),
@@ -43,6 +48,21 @@ pub fn DashboardDeck() -> impl IntoView {
 			title: "Fear & Greed".into(),
 			content: Arc::new(|| fng::FngView().into_any()),
 		},
+		DockPanel {
+			id: PanelId("funding".into()),
+			title: "Funding & OI".into(),
+			content: Arc::new(|| funding::FundingView().into_any()),
+		},
+		DockPanel {
+			id: PanelId("liquidations".into()),
+			title: "Liquidations".into(),
+			content: Arc::new(|| liquidations::LiquidationsView().into_any()),
+		},
+		DockPanel {
+			id: PanelId("depth".into()),
+			title: "Depth".into(),
+			content: Arc::new(|| depth::DepthView().into_any()),
+		},
 	]);
 
 	// All client wiring lives here: `on_ready` fires once on the client with a `Copy` (`!Send`)
@@ -135,13 +155,17 @@ fn seed_key(bp: Breakpoint) -> &'static str {
 /// the chart keeps an elastic-but-sane range.
 fn seed(api: &PackedApi) {
 	api.reset();
-	let specs: [(&str, u32, u32, MinSize); 5] = [
+	let specs: [(&str, u32, u32, MinSize); 9] = [
 		// floored at the current live session size — these two never work any smaller
 		("market_structure", 29, 16, MinSize::Steps { w: Step(29), h: Step(16) }),
 		("lsr", 22, 16, MinSize::Steps { w: Step(11), h: Step(9) }),
+		("pair", 29, 14, MinSize::Rem { w: 24.0, h: 12.0 }),
 		("cme", 20, 12, MinSize::Rem { w: 24.0, h: 8.0 }),
-		("vol", 14, 4, MinSize::Rem { w: 16.0, h: 3.0 }),
+		("vol", 20, 16, MinSize::Rem { w: 20.0, h: 12.0 }),
 		("fng", 16, 4, MinSize::Rem { w: 20.0, h: 3.0 }),
+		("funding", 26, 14, MinSize::Rem { w: 28.0, h: 10.0 }),
+		("liquidations", 29, 14, MinSize::Rem { w: 24.0, h: 12.0 }),
+		("depth", 22, 12, MinSize::Rem { w: 26.0, h: 8.0 }),
 	];
 	debug_assert!(
 		specs.len() == PANEL_IDS.len() && PANEL_IDS.iter().all(|id| specs.iter().any(|(s, ..)| s == id)),
@@ -0,0 +1,76 @@
+use color_eyre::eyre::{Result, WrapErr as _, bail};
+use serde::Deserialize;
+use serde_with::{DisplayFromStr, serde_as};
+
+use super::{BAND, DepthSnapshot, Level, SideDepth, TOP_LEVELS};
+use crate::dashboards::_core::{SourceParams, cassette};
+
+/// Levels Binance returns per side at most.
+const BOOK_LIMIT: &str = "1000";
+
+#[derive(Clone, Debug, Default)]
+pub struct DepthParams {
+	/// E.g. `BTC-USDT`.
+	pub pair: String,
+}
+impl SourceParams for DepthParams {
+	fn key(&self) -> String {
+		self.pair.clone()
+	}
+}
+
+/// Binance's L2 book for a USDT perp, cut to [`BAND`] around mid. Only the largest [`TOP_LEVELS`] a side are kept
+/// level by level: walls are the biggest levels by definition, and the rest only matter through the averages.
+pub async fn snapshot(pair: &str) -> Result<DepthSnapshot> {
+	let symbol = pair.replace('-', "");
+	let body = cassette::http(&format!("binance/depth/{symbol}/{BOOK_LIMIT}"), async {
+		Ok(reqwest::Client::new()
+			.get("https://fapi.binance.com/fapi/v1/depth")
+			.query(&[("symbol", symbol.as_str()), ("limit", BOOK_LIMIT)])
+			.send()
+			.await?
+			.error_for_status()?
+			.text()
+			.await?)
+	})
+	.await?;
+
+	// levels are `[price, quantity]`, both as strings, best first
+	#[serde_as]
+	#[derive(Deserialize)]
+	struct Book {
+		#[serde_as(as = "Vec<(DisplayFromStr, DisplayFromStr)>")]
+		bids: Vec<(f64, f64)>,
+		#[serde_as(as = "Vec<(DisplayFromStr, DisplayFromStr)>")]
+		asks: Vec<(f64, f64)>,
+	}
+	let book: Book = serde_json::from_str(&body).wrap_err_with(|| format!("malformed depth for {symbol}"))?;
+	let (Some(&(bid, _)), Some(&(ask, _))) = (book.bids.first(), book.asks.first()) else {
+		bail!("empty {symbol} order book");
+	};
+	let mid = (bid + ask) / 2.0;
+
+	let side = |levels: &[(f64, f64)]| {
+		let mut in_band: Vec<Level> = levels
+			.iter()
+			.filter(|(price, _)| (price / mid - 1.0).abs() <= BAND)
+			.map(|&(price, qty)| Level { price, usd: price * qty })
+			.collect();
+		let reach = in_band.last().map_or(0.0, |l| (l.price / mid - 1.0).abs());
+		let total_usd = in_band.iter().map(|l| l.usd).sum();
+		let levels = in_band.len();
+		in_band.sort_by(|a, b| b.usd.total_cmp(&a.usd));
+		in_band.truncate(TOP_LEVELS);
+		SideDepth {
+			total_usd,
+			levels,
+			reach,
+			largest: in_band,
+		}
+	};
+	Ok(DepthSnapshot {
+		mid,
+		bids: side(&book.bids),
+		asks: side(&book.asks),
+	})
+}
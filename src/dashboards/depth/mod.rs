@@ -0,0 +1,462 @@
+//! Order book walls: levels much larger than the book's average around them. Each configured pair's L2 book is
+//! snapshotted every minute; the panel flags the walls in the latest one and, from the last hour of snapshots,
+//! how long each has stood and which were added, pulled or filled.
+#[cfg(feature = "ssr")]
+mod data;
+
+use leptos::{html::*, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use super::{LoadingIndicator, LoadingIndicatorProps};
+
+/// How far either side of mid the book is read, as a fraction of mid.
+#[cfg_attr(not(feature = "ssr"), allow(dead_code))]
+const BAND: f64 = 0.01;
+/// Levels a side kept per snapshot, largest first; walls beyond these go unseen.
+#[cfg_attr(not(feature = "ssr"), allow(dead_code))]
+const TOP_LEVELS: usize = 25;
+/// Snapshots the wall ages and changes are read from.
+#[cfg_attr(not(feature = "ssr"), allow(dead_code))]
+const HISTORY_SECS: i64 = 3600;
+/// Most recent wall changes listed.
+#[cfg_attr(not(feature = "ssr"), allow(dead_code))]
+const CHANGES: usize = 12;
+
+#[component]
+pub fn DepthView() -> impl IntoView {
+	let trigger = RwSignal::new(());
+	// `None` until the reader switches: the server opens on the first configured pair
+	let pair = RwSignal::new(None::<String>);
+	let pairs = Resource::new(|| (), |_| depth_pairs());
+	let depth_resource = Resource::new(move || (pair.get(), trigger.get()), |(pair, _)| depth_panel(pair));
+
+	// Refetch the moment the server persists a fresh snapshot of any pair
+	#[cfg(feature = "hydrate")]
+	super::on_source_event_where(
+		|s| s.starts_with("DepthSnapshot-"),
+		move |ev| {
+			if let super::SourceEvent::Refreshed { .. } = ev {
+				trigger.update(|_| ());
+			}
+		},
+	);
+
+	// Set up retry interval - retry every 1 minute on error
+	#[cfg(not(feature = "ssr"))]
+	{
+		Effect::new(move || {
+			if let Some(Err(_)) = depth_resource.get() {
+				set_timeout(
+					move || {
+						trigger.update(|_| ());
+					},
+					std::time::Duration::from_secs(60),
+				);
+			}
+		});
+	}
+
+	let switcher = move || {
+		let Some(Ok(pairs)) = pairs.get() else { return None };
+		(pairs.len() > 1).then(|| {
+			select()
+				.class("mb-2 p-1 border rounded text-sm")
+				.on(leptos::ev::change, move |ev| pair.set(Some(event_target_value(&ev))))
+				.child(
+					pairs
+						.into_iter()
+						.enumerate()
+						.map(|(i, p)| option().value(p.clone()).selected(pair.get().map_or(i == 0, |sel| sel == p)).child(p))
+						.collect::<Vec<_>>(),
+				)
+		})
+	};
+
+	div().class("panel-center").child(div().class("w-full max-h-full overflow-auto").child((
+		switcher,
+		Suspense(SuspenseProps {
+			fallback: { || LoadingIndicator(LoadingIndicatorProps { label: "Depth".into() }) }.into(),
+			children: ToChildren::to_children(move || {
+				IntoRender::into_render(move || match depth_resource.get() {
+					Some(Ok(panel)) => depth_view(&panel).into_any(),
+					Some(Err(e)) => (pre().child(format!("Error loading order book depth: {e} (retrying...)")),).into_any(),
+					None => (LoadingIndicator(LoadingIndicatorProps { label: "Depth".into() }),).into_any(),
+				})
+			}),
+		}),
+	)))
+}
+
+/// One pair's book around mid at one moment.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct DepthSnapshot {
+	mid: f64,
+	bids: SideDepth,
+	asks: SideDepth,
+}
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct SideDepth {
+	/// Resting within [`BAND`] of mid, in USD.
+	total_usd: f64,
+	/// Price levels within the band.
+	levels: usize,
+	/// How far from mid the band actually reached, as a fraction of mid: short of [`BAND`] when Binance's level cap
+	/// ran out first.
+	reach: f64,
+	/// The [`TOP_LEVELS`] largest levels, largest first.
+	largest: Vec<Level>,
+}
+#[cfg(feature = "ssr")]
+impl SideDepth {
+	fn mean_usd(&self) -> f64 {
+		self.total_usd / self.levels.max(1) as f64
+	}
+}
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct Level {
+	price: f64,
+	usd: f64,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+enum Side {
+	Bid,
+	Ask,
+}
+
+/// What the panel shows for one pair.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct DepthPanel {
+	pair: String,
+	mid: f64,
+	bid_usd: f64,
+	ask_usd: f64,
+	/// Of the nearer side's, in %.
+	reach_pct: f64,
+	wall_multiple: f64,
+	/// Nearest mid first.
+	walls: Vec<Wall>,
+	/// Newest first.
+	changes: Vec<WallChange>,
+	/// Snapshots `changes` was read from.
+	snapshots: usize,
+	/// Stretches between consecutive snapshots too long to say what happened in them; skipped.
+	#[serde(default)]
+	gaps: usize,
+}
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct Wall {
+	side: Side,
+	price: f64,
+	usd: f64,
+	/// Of the side's average level.
+	multiple: f64,
+	/// From mid, in %.
+	distance_pct: f64,
+	/// How long it's been standing, as far back as the history reaches; `None` if it's new.
+	age_secs: Option<i64>,
+}
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct WallChange {
+	ago_secs: i64,
+	side: Side,
+	price: f64,
+	usd: f64,
+	kind: ChangeKind,
+}
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+enum ChangeKind {
+	Added,
+	/// Gone with mid still on this side of it.
+	Pulled,
+	/// Gone with mid through it: traded into rather than cancelled.
+	Filled,
+}
+
+/// `(side, level, multiple of the side's average)` of every wall in `s`.
+#[cfg(feature = "ssr")]
+fn walls(s: &DepthSnapshot, multiple: f64) -> Vec<(Side, &Level, f64)> {
+	[(Side::Bid, &s.bids), (Side::Ask, &s.asks)]
+		.into_iter()
+		.flat_map(|(side, d)| {
+			let mean = d.mean_usd();
+			d.largest.iter().filter(move |l| mean > 0.0 && l.usd >= multiple * mean).map(move |l| (side, l, l.usd / mean))
+		})
+		.collect()
+}
+
+/// Walls of the latest snapshot with their ages, and what changed between consecutive snapshots in `history`. Snapshots
+/// more than two refresh intervals apart are a gap, not a step: a wall isn't aged across one, and nothing's read off it.
+#[cfg(feature = "ssr")]
+fn panel(pair: String, current: &DepthSnapshot, history: &[super::_core::Snapshot<DepthSnapshot>], multiple: f64) -> DepthPanel {
+	use super::_core::SourceData as _;
+
+	let now = jiff::Timestamp::now().as_second();
+	let max_step = 2 * DepthSnapshot::decay_horizon().duration().as_secs() as i64;
+	let has = |s: &DepthSnapshot, side: Side, price: f64| walls(s, multiple).iter().any(|(sd, l, _)| *sd == side && l.price == price);
+
+	let mut current_walls: Vec<Wall> = walls(current, multiple)
+		.into_iter()
+		.map(|(side, l, m)| {
+			// walk back through the snapshots for as long as the wall was there, and they're close enough to say so
+			let mut since: Option<i64> = None;
+			for s in history.iter().rev() {
+				let t = s.fetched_at.as_second();
+				if !has(&s.data, side, l.price) || since.is_some_and(|later| later - t > max_step) {
+					break;
+				}
+				since = Some(t);
+			}
+			Wall {
+				side,
+				price: l.price,
+				usd: l.usd,
+				multiple: m,
+				distance_pct: (l.price / current.mid - 1.0) * 100.0,
+				age_secs: since.map(|t| now - t).filter(|age| *age > 0),
+			}
+		})
+		.collect();
+	current_walls.sort_by(|a, b| a.distance_pct.abs().total_cmp(&b.distance_pct.abs()));
+
+	let mut changes = Vec::new();
+	let mut gaps = 0;
+	for w in history.windows(2) {
+		if w[1].fetched_at.as_second() - w[0].fetched_at.as_second() > max_step {
+			gaps += 1;
+			continue;
+		}
+		let (before, after) = (&w[0].data, &w[1].data);
+		let ago_secs = now - w[1].fetched_at.as_second();
+		for (side, l, _) in walls(after, multiple) {
+			if !has(before, side, l.price) {
+				changes.push(WallChange {
+					ago_secs,
+					side,
+					price: l.price,
+					usd: l.usd,
+					kind: ChangeKind::Added,
+				});
+			}
+		}
+		for (side, l, _) in walls(before, multiple) {
+			if has(after, side, l.price) {
+				continue;
+			}
+			let crossed = match side {
+				Side::Bid => after.mid <= l.price,
+				Side::Ask => after.mid >= l.price,
+			};
+			changes.push(WallChange {
+				ago_secs,
+				side,
+				price: l.price,
+				usd: l.usd,
+				kind: if crossed { ChangeKind::Filled } else { ChangeKind::Pulled },
+			});
+		}
+	}
+	changes.reverse();
+	changes.truncate(CHANGES);
+
+	DepthPanel {
+		pair,
+		mid: current.mid,
+		bid_usd: current.bids.total_usd,
+		ask_usd: current.asks.total_usd,
+		reach_pct: current.bids.reach.min(current.asks.reach) * 100.0,
+		wall_multiple: multiple,
+		walls: current_walls,
+		changes,
+		snapshots: history.len(),
+		gaps,
+	}
+}
+
+fn depth_view(p: &DepthPanel) -> impl IntoView {
+	let side_color = |side: Side| match side {
+		Side::Bid => "#16a34a",
+		Side::Ask => "#dc2626",
+	};
+	let side_label = |side: Side| match side {
+		Side::Bid => "bid",
+		Side::Ask => "ask",
+	};
+	let imbalance = (p.bid_usd - p.ask_usd) / (p.bid_usd + p.ask_usd).max(f64::MIN_POSITIVE) * 100.0;
+
+	let walls = p
+		.walls
+		.iter()
+		.map(|w| {
+			tr().child((
+				td().class("px-2 text-left").style(format!("color: {}", side_color(w.side))).child(side_label(w.side)),
+				td().class("px-2 text-right").child(price(w.price)),
+				td().class("px-2 text-right").child(format!("{:+.2}%", w.distance_pct)),
+				td().class("px-2 text-right").child(usd(w.usd)),
+				td().class("px-2 text-right").child(format!("{:.0}×", w.multiple)),
+				td().class("px-2 text-right").child(w.age_secs.map_or_else(|| "new".to_owned(), ago)),
+			))
+		})
+		.collect::<Vec<_>>();
+	let changes = p
+		.changes
+		.iter()
+		.map(|c| {
+			let (mark, verb) = match c.kind {
+				ChangeKind::Added => ("+", "added"),
+				ChangeKind::Pulled => ("−", "pulled"),
+				ChangeKind::Filled => ("✓", "filled"),
+			};
+			div().child((
+				span().style(format!("color: {}", side_color(c.side))).child(format!("{mark} {} wall ", side_label(c.side))),
+				format!("{} {} {verb}, {} ago", price(c.price), usd(c.usd), ago(c.ago_secs)),
+			))
+		})
+		.collect::<Vec<_>>();
+
+	div().class("text-sm").child((
+		div().child((
+			span().class("font-bold").child(format!("{} {}", p.pair, price(p.mid))),
+			span()
+				.class("ml-2")
+				.child(format!("bids {} / asks {} within ±{:.2}% ", usd(p.bid_usd), usd(p.ask_usd), p.reach_pct)),
+			span()
+				.style(format!("color: {}", if imbalance >= 0.0 { "#16a34a" } else { "#dc2626" }))
+				.child(format!("({imbalance:+.0}%)")),
+		)),
+		if walls.is_empty() {
+			div().class("mt-2 text-gray-500").child(format!("No level at {:.0}× the average", p.wall_multiple)).into_any()
+		} else {
+			table()
+				.class("mx-auto mt-2 font-mono")
+				.child((
+					thead().child(tr().child((
+						th().class("px-2"),
+						th().class("px-2 text-right").child("price"),
+						th().class("px-2 text-right").child("from mid"),
+						th().class("px-2 text-right").child("size"),
+						th().class("px-2 text-right").child("× avg"),
+						th().class("px-2 text-right").child("standing"),
+					))),
+					tbody().child(walls),
+				))
+				.into_any()
+		},
+		div().class("mt-2 text-xs font-mono").child(changes),
+		div().class("mt-1 text-xs text-gray-500").child((
+			format!(
+				"Binance perp book; walls ≥ {:.0}× the side's average level; changes over the last {} snapshots",
+				p.wall_multiple, p.snapshots
+			),
+			(p.gaps > 0).then(|| span().style("color: #d97706").child(format!(" ({} gaps skipped)", p.gaps))),
+		)),
+	))
+}
+
+fn price(v: f64) -> String {
+	match v {
+		v if v >= 1000.0 => format!("{v:.1}"),
+		v if v >= 1.0 => format!("{v:.3}"),
+		v => format!("{v:.6}"),
+	}
+}
+
+fn usd(v: f64) -> String {
+	match v {
+		v if v >= 1e9 => format!("${:.2}B", v / 1e9),
+		v if v >= 1e6 => format!("${:.1}M", v / 1e6),
+		v => format!("${:.0}K", v / 1e3),
+	}
+}
+
+fn ago(secs: i64) -> String {
+	match secs {
+		s if s < 90 => format!("{s}s"),
+		s if s < 90 * 60 => format!("{}m", s / 60),
+		s => format!("{}h", s / 3600),
+	}
+}
+
+#[server]
+async fn depth_pairs() -> Result<Vec<String>, ServerFnError> {
+	Ok(crate::app::server_impl::get_settings()?.depth.pairs)
+}
+
+#[server]
+async fn depth_panel(pair: Option<String>) -> Result<DepthPanel, ServerFnError> {
+	let conf = crate::app::server_impl::get_settings()?.depth;
+	let pair = match pair {
+		None => conf.pairs.first().cloned().ok_or_else(|| ServerFnError::new("No depth pairs configured"))?,
+		Some(pair) if conf.pairs.contains(&pair) => pair,
+		Some(pair) => return Err(ServerFnError::new(format!("{pair} is not a configured depth pair"))),
+	};
+	let params = data::DepthParams { pair: pair.clone() };
+	let current = super::_core::load_with::<DepthSnapshot>(&params).await.map_err(|e| {
+		tracing::error!("Failed to load {pair} depth: {e:?}");
+		ServerFnError::new(format!("Failed to load {pair} depth: {e}"))
+	})?;
+	let since = jiff::Timestamp::now() - jiff::SignedDuration::from_secs(HISTORY_SECS);
+	// without history there are still walls; only their ages and the changes go missing
+	let history = super::_core::load_history_with::<DepthSnapshot>(&params, since..).unwrap_or_else(|e| {
+		tracing::warn!("Failed to read {pair} depth history: {e:?}");
+		Vec::new()
+	});
+	Ok(panel(pair, &current.data, &history, conf.wall_multiple))
+}
+
+/// Keeps every configured pair's book warm; the list is read once here, so adding one takes a restart. A snapshot is
+/// one weight-20 call, so it doesn't queue for a refresh permit behind the big fan-outs.
+#[cfg(feature = "ssr")]
+pub(super) fn register(scheduler: &mut super::_core::Scheduler, settings: &crate::config::LiveSettings) {
+	let pairs = settings.config().map(|s| s.depth.pairs).unwrap_or_else(|_| crate::config::DepthConf::default().pairs);
+	for pair in pairs {
+		scheduler.register_cheap_with::<DepthSnapshot>(data::DepthParams { pair });
+	}
+}
+#[cfg(feature = "ssr")]
+impl super::_core::SourceData for DepthSnapshot {
+	type Params = data::DepthParams;
+
+	/// Walls come and go within minutes; any slower and a pulled one is never seen.
+	fn decay_horizon() -> v_utils::trades::Timeframe {
+		"1m".into()
+	}
+
+	/// Only the last [`HISTORY_SECS`] is ever read; at one snapshot a minute, anything more is dead weight.
+	fn history_retention() -> Option<jiff::SignedDuration> {
+		Some(jiff::SignedDuration::from_secs(HISTORY_SECS + 5 * 60))
+	}
+
+	async fn fetch(params: &data::DepthParams) -> color_eyre::eyre::Result<Self> {
+		data::snapshot(&params.pair).await
+	}
+
+	/// A thin, even book with one wall a side: a big ask just overhead and a bid further down.
+	fn fixture(params: &data::DepthParams) -> Option<Self> {
+		let mid = match params.pair.as_str() {
+			"BTC-USDT" => 67_500.0,
+			"ETH-USDT" => 3_450.0,
+			_ => 150.0,
+		};
+		let side = |dir: f64, wall_at: usize, wall_usd: f64| {
+			let mut largest: Vec<Level> = (1..=TOP_LEVELS)
+				.map(|i| Level {
+					price: mid * (1.0 + dir * 0.0004 * i as f64),
+					usd: if i == wall_at { wall_usd } else { 90_000.0 - 1_500.0 * i as f64 },
+				})
+				.collect();
+			largest.sort_by(|a, b| b.usd.total_cmp(&a.usd));
+			SideDepth {
+				total_usd: largest.iter().map(|l| l.usd).sum::<f64>() + 150.0 * 20_000.0,
+				levels: TOP_LEVELS + 150,
+				reach: BAND,
+				largest,
+			}
+		};
+		Some(Self {
+			mid,
+			bids: side(-1.0, 14, 2_400_000.0),
+			asks: side(1.0, 3, 4_100_000.0),
+		})
+	}
+}
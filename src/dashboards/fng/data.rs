@@ -4,6 +4,8 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_with::{DisplayFromStr, serde_as};
 
+use crate::dashboards::_core::cassette;
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct FngResponse {
 	name: String,
@@ -30,26 +32,39 @@ pub struct Metadata {
 	error: Option<String>,
 }
 
-#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 pub struct Fng {
 	pub value: f64,
+	/// alternative.me's own bucket for `value`, e.g. `Extreme Fear`.
+	pub classification: String,
 	pub timestamp: Timestamp,
 }
 
-/// Is expected to be most often used wtih `limit=1`, so not exposing [Duration](std::time::Duration) instead of it.
+/// The index over the last [`HISTORY_DAYS`], oldest first.
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct FngHistory {
+	pub points: Vec<Fng>,
+}
+pub const HISTORY_DAYS: usize = 90;
+
+/// Newest first, one point per published value (daily, despite the name). Taking a count rather than a
+/// [Duration](std::time::Duration) keeps it what the API itself accepts.
 pub async fn btc_fngs_hourly(limit: usize) -> Result<Vec<Fng>> {
-	let client = Client::new();
-	let response = client
-		.get(format!("https://api.alternative.me/fng/?limit={limit}"))
-		.send()
-		.await
-		.map_err(|e| eyre!("Failed to fetch Fear and Greed Index: {e}"))?;
+	let body = cassette::http(&format!("fng/limit={limit}"), async {
+		let response = Client::new()
+			.get(format!("https://api.alternative.me/fng/?limit={limit}"))
+			.send()
+			.await
+			.map_err(|e| eyre!("Failed to fetch Fear and Greed Index: {e}"))?;
 
-	if !response.status().is_success() {
-		bail!("Failed to fetch Fear and Greed Index. Status: {}", response.status());
-	}
+		if !response.status().is_success() {
+			bail!("Failed to fetch Fear and Greed Index. Status: {}", response.status());
+		}
+		response.text().await.map_err(|e| eyre!("Failed to read Fear and Greed Index response: {e}"))
+	})
+	.await?;
 
-	let fng_response: FngResponse = response.json().await.map_err(|e| eyre!("Failed to parse Fear and Greed Index response: {e}"))?;
+	let fng_response: FngResponse = serde_json::from_str(&body).map_err(|e| eyre!("Failed to parse Fear and Greed Index response: {e}"))?;
 	if let Some(error) = fng_response.metadata.error {
 		if !error.is_empty() {
 			bail!("API returned an error: {error}");
@@ -61,7 +76,23 @@ pub async fn btc_fngs_hourly(limit: usize) -> Result<Vec<Fng>> {
 		.map(|raw| -> Result<Fng> {
 			let timestamp = Timestamp::from_second(raw.ts_seconds).map_err(|e| eyre!("Invalid timestamp {}: {e}", raw.ts_seconds))?;
 
-			Ok(Fng { value: raw.value, timestamp })
+			Ok(Fng {
+				value: raw.value,
+				classification: raw.value_classification,
+				timestamp,
+			})
 		})
 		.collect()
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn newest_first() {
+		let fngs = cassette::replay(btc_fngs_hourly(3)).await.unwrap();
+		let got: Vec<(f64, &str, i64)> = fngs.iter().map(|f| (f.value, f.classification.as_str(), f.timestamp.as_second())).collect();
+		assert_eq!(got, [(64.0, "Greed", 1_760_054_400), (70.0, "Greed", 1_759_968_000), (24.0, "Extreme Fear", 1_759_881_600)]);
+	}
+}
@@ -9,6 +9,14 @@ pub fn FngView() -> impl IntoView {
 	let trigger = RwSignal::new(());
 	let fng_resource = Resource::new(move || trigger.get(), |_| async move { try_build().await });
 
+	// Refetch the moment the server persists a fresh copy
+	#[cfg(feature = "hydrate")]
+	super::on_source_event("FngHistory", move |ev| {
+		if let super::SourceEvent::Refreshed { .. } = ev {
+			trigger.update(|_| ());
+		}
+	});
+
 	// Set up retry interval - retry every 1 minute on error
 	#[cfg(not(feature = "ssr"))]
 	{
@@ -28,43 +36,179 @@ pub fn FngView() -> impl IntoView {
 		fallback: { || LoadingIndicator(LoadingIndicatorProps { label: "Fear & Greed".into() }) }.into(),
 		#[rustfmt::skip]
 		children: ToChildren::to_children(move || IntoRender::into_render(move || match fng_resource.get() {
-			Some(Ok(fng_data)) => (pre().child(fng_data.0),).into_any(),
+			Some(Ok(fng_data)) => fng_summary(&fng_data).into_any(),
 			Some(Err(e)) => (pre().child(format!("Error loading Fear & Greed: {e} (retrying...)")),).into_any(),
 			None => (LoadingIndicator(LoadingIndicatorProps { label: "Fear & Greed".into() }),).into_any(),
 		})),
 	}))
 }
 
-#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize, derive_new::new)]
-pub struct FngRendered(String);
+/// Latest reading with how far it moved, and the [`HISTORY_DAYS`](data::HISTORY_DAYS) behind it.
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct FngRendered {
+	value: f64,
+	classification: String,
+	/// `(lookback label, points moved since then)`; `None` when history doesn't reach back that far.
+	changes: Vec<(String, Option<f64>)>,
+	/// `(UNIX seconds, value)`, oldest first.
+	history: Vec<(i64, f64)>,
+}
+
+fn fng_summary(f: &FngRendered) -> impl IntoView {
+	let changes = f
+		.changes
+		.iter()
+		.map(|(label, change)| {
+			let (text, color) = match change {
+				Some(c) => (
+					format!("{c:+.0}"),
+					if *c > 0.0 {
+						"#16a34a"
+					} else if *c < 0.0 {
+						"#dc2626"
+					} else {
+						"inherit"
+					},
+				),
+				None => ("–".to_owned(), "inherit"),
+			};
+			span().child((format!("{label} "), span().style(format!("color: {color}")).child(text)))
+		})
+		.collect::<Vec<_>>();
+
+	div().class("w-full").child((
+		div().child((
+			span()
+				.class("text-2xl font-bold")
+				.style(format!("color: {}", fng_color(f.value)))
+				.child(format!("{:.0}", f.value)),
+			span().class("ml-2").child(f.classification.clone()),
+		)),
+		div().class("text-xs text-gray-500").child("BTC Fear and Greed (alternative.me)"),
+		div().class("flex justify-center gap-x-3 text-sm mt-1").child(changes),
+		sparkline(&f.history),
+	))
+}
+
+/// Inline SVG of the index on its fixed 0–100 scale, with the neutral 50 dashed.
+fn sparkline(history: &[(i64, f64)]) -> impl IntoView {
+	const W: f64 = 300.0;
+	const H: f64 = 60.0;
+	let (t0, t1) = (history.first().map_or(0, |p| p.0), history.last().map_or(0, |p| p.0));
+	let x = |t: i64| if t1 > t0 { (t - t0) as f64 / (t1 - t0) as f64 * W } else { W };
+	let y = |v: f64| (100.0 - v) / 100.0 * H;
+	let pts = history.iter().map(|(t, v)| format!("{:.1},{:.1}", x(*t), y(*v))).collect::<Vec<_>>().join(" ");
+	let color = history.last().map_or("#94a3b8", |p| fng_color(p.1));
+
+	div().class("mt-2").child((
+		leptos::svg::svg()
+			.attr("viewBox", format!("0 0 {W} {H}"))
+			.attr("preserveAspectRatio", "none")
+			.class("w-full h-16")
+			.child((
+				leptos::svg::line()
+					.attr("x1", "0")
+					.attr("x2", W.to_string())
+					.attr("y1", y(50.0).to_string())
+					.attr("y2", y(50.0).to_string())
+					.attr("stroke", "#94a3b8")
+					.attr("stroke-dasharray", "3 3")
+					.attr("vector-effect", "non-scaling-stroke"),
+				leptos::svg::polyline()
+					.attr("points", pts)
+					.attr("fill", "none")
+					.attr("stroke", color)
+					.attr("stroke-width", "1.5")
+					.attr("vector-effect", "non-scaling-stroke"),
+			)),
+		div().class("text-xs text-gray-500").child(format!("last {} days", history.len())),
+	))
+}
+
+/// Red through grey to green, on alternative.me's own bucket edges.
+fn fng_color(value: f64) -> &'static str {
+	match value {
+		v if v < 25.0 => "#dc2626",
+		v if v < 47.0 => "#f97316",
+		v if v < 55.0 => "#64748b",
+		v if v < 76.0 => "#65a30d",
+		_ => "#16a34a",
+	}
+}
+
 #[server]
 async fn try_build() -> Result<FngRendered, ServerFnError> {
-	let fng = super::_core::load::<data::Fng>()
+	let fng = super::_core::load::<data::FngHistory>()
 		.await
 		.map_err(|e| {
 			tracing::error!("Failed to load Fear & Greed Index: {e:?}");
 			ServerFnError::new(format!("Failed to load Fear & Greed Index: {e}"))
 		})?
 		.data;
-	Ok(fng.into())
+	fng.try_into().map_err(|e: color_eyre::eyre::Report| ServerFnError::new(e.to_string()))
+}
+#[cfg(feature = "ssr")]
+pub(super) fn register(scheduler: &mut super::_core::Scheduler) {
+	scheduler.register::<data::FngHistory>();
 }
 #[cfg(feature = "ssr")]
-impl super::_core::SourceData for data::Fng {
+impl super::_core::SourceData for data::FngHistory {
+	type Params = ();
+
 	fn decay_horizon() -> v_utils::trades::Timeframe {
 		"1h".into()
 	}
 
-	async fn fetch() -> color_eyre::eyre::Result<Self> {
-		data::btc_fngs_hourly(1)
-			.await?
-			.into_iter()
-			.next()
-			.ok_or_else(|| color_eyre::eyre::eyre!("Fear & Greed Index response was empty"))
+	async fn fetch(_: &()) -> color_eyre::eyre::Result<Self> {
+		let mut points = data::btc_fngs_hourly(data::HISTORY_DAYS).await?;
+		if points.is_empty() {
+			color_eyre::eyre::bail!("Fear & Greed Index response was empty");
+		}
+		points.sort_by_key(|p| p.timestamp);
+		Ok(Self { points })
+	}
+
+	/// A slow swing between fear and greed, landing on 62.
+	fn fixture(_: &()) -> Option<Self> {
+		const END: i64 = 1_760_000_000;
+		let n = data::HISTORY_DAYS as i64;
+		let swing = |i: i64| 20.0 * (i as f64 / 30.0).sin() + 6.0 * (i as f64 / 4.0).sin();
+		let points = (0..n)
+			.map(|i| {
+				let value = (62.0 + swing(i) - swing(n - 1)).round().clamp(0.0, 100.0);
+				let classification = match value {
+					v if v < 25.0 => "Extreme Fear",
+					v if v < 47.0 => "Fear",
+					v if v < 55.0 => "Neutral",
+					v if v < 76.0 => "Greed",
+					_ => "Extreme Greed",
+				};
+				data::Fng {
+					value,
+					classification: classification.to_owned(),
+					timestamp: jiff::Timestamp::from_second(END - (n - 1 - i) * 86_400).expect("in range"),
+				}
+			})
+			.collect();
+		Some(Self { points })
 	}
 }
 #[cfg(feature = "ssr")]
-impl From<data::Fng> for FngRendered {
-	fn from(fng: data::Fng) -> Self {
-		Self(format!("BTC Fear and Greed (alternative.me): {}", fng.value))
+impl TryFrom<data::FngHistory> for FngRendered {
+	type Error = color_eyre::eyre::Report;
+
+	fn try_from(h: data::FngHistory) -> color_eyre::eyre::Result<Self> {
+		let latest = h.points.last().ok_or_else(|| color_eyre::eyre::eyre!("no Fear & Greed readings"))?;
+		// an hour of slack: a day's value isn't always stamped on the same second
+		let since = |days: i64| {
+			let target = latest.timestamp.as_second() - days * 86_400 + 3600;
+			h.points.iter().rev().find(|p| p.timestamp.as_second() <= target).map(|then| latest.value - then.value)
+		};
+		Ok(Self {
+			value: latest.value,
+			classification: latest.classification.clone(),
+			changes: [("1d", 1), ("7d", 7), ("30d", 30)].into_iter().map(|(label, days)| (label.to_owned(), since(days))).collect(),
+			history: h.points.iter().map(|p| (p.timestamp.as_second(), p.value)).collect(),
+		})
 	}
 }
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+use color_eyre::eyre::{Result, WrapErr as _};
+use futures::{StreamExt as _, stream};
+use serde::Deserialize;
+use serde_with::{DisplayFromStr, serde_as};
+use tracing::{info, instrument, warn};
+use v_exchanges::prelude::*;
+use v_utils::trades::Pair;
+
+use super::{FundingOi, PerpFunding};
+use crate::dashboards::_core::{Progress, cassette, load_history};
+
+/// Funding interval of every perp `fundingInfo` doesn't list.
+const DEFAULT_INTERVAL_HOURS: u32 = 8;
+/// Binance caps `fundingRate` pages at this many rows.
+const FUNDING_PAGE: usize = 1000;
+/// `openInterest` calls in flight at once.
+const OI_CONCURRENCY: usize = 10;
+
+/// Funding and open interest of every Binance USDT perp. A perp whose OI call fails still gets its row, just
+/// without the OI figures; only the funding calls failing fails the whole fetch.
+///
+/// Open interest is the live `/fapi/v1/openInterest` figure, and its 24h change is against this source's own poll
+/// a day back. Binance's `openInterestHist` would give the change directly, but it's one of the `/futures/data`
+/// endpoints, whose 1000-per-5m IP budget the LSR dashboard's ratio calls already spend most of.
+#[instrument]
+pub async fn get() -> Result<FundingOi> {
+	let mut ex = ExchangeName::Binance.init_client();
+	ex.set_retry_config(RetryConfig {
+		max_retries: 3,
+		..Default::default()
+	});
+	ex.set_timeout(std::time::Duration::from_secs(60));
+	let pairs: Vec<Pair> = cassette::tape("binance/Perp/usdt_pairs", async { Ok(ex.exchange_info(Instrument::Perp).await?.usdt_pairs().collect()) }).await?;
+
+	let client = reqwest::Client::new();
+	let since_ms = jiff::Timestamp::now().as_millisecond() - 24 * 3_600_000;
+	let (current, settled, intervals) = tokio::try_join!(premium_index(&client), funding_since(&client, since_ms), funding_intervals(&client))?;
+	let day_ago = oi_day_ago();
+
+	let progress = Progress::of::<FundingOi>(&());
+	progress.set_target(pairs.len());
+	let client = &client;
+	let rows = pairs.iter().map(|p| {
+		let symbol = p.to_string().replace('-', "");
+		let premium = current.get(&symbol).copied();
+		let settled = settled.get(&symbol);
+		let then = day_ago.get(&p.to_string()).copied();
+		let interval_hours = intervals.get(&symbol).copied().unwrap_or(DEFAULT_INTERVAL_HOURS);
+		// to % per 8h, so perps settling at different intervals rank and average together
+		let per_8h = 100.0 * 8.0 / interval_hours as f64;
+		async move {
+			let Some((funding, mark_price)) = premium else {
+				progress.inc();
+				return None;
+			};
+			let oi = match open_interest(client, &symbol).await {
+				Ok(contracts) => Some(contracts * mark_price),
+				Err(e) => {
+					warn!("Couldn't fetch open interest for {symbol}: {e:?}");
+					None
+				}
+			};
+			progress.inc();
+			Some(PerpFunding {
+				pair: p.to_string(),
+				funding: funding * per_8h,
+				funding_24h: settled.filter(|s| !s.is_empty()).map(|s| s.iter().sum::<f64>() / s.len() as f64 * per_8h),
+				interval_hours,
+				oi_usd: oi,
+				oi_change_24h: oi.zip(then).and_then(|(now, then)| (then > 0.0).then_some((now / then - 1.0) * 100.0)),
+			})
+		}
+	});
+	let perps: Vec<PerpFunding> = stream::iter(rows).buffer_unordered(OI_CONCURRENCY).filter_map(|p| async move { p }).collect().await;
+	info!(
+		"Binance funding: {}/{} pairs loaded, {} with open interest",
+		perps.len(),
+		pairs.len(),
+		perps.iter().filter(|p| p.oi_usd.is_some()).count()
+	);
+	Ok(FundingOi { perps, total_pairs: pairs.len() })
+}
+
+async fn binance(client: &reqwest::Client, key: &str, path: &str, query: &[(&str, &str)]) -> Result<String> {
+	cassette::http(&format!("binance/{key}"), async {
+		Ok(client
+			.get(format!("https://fapi.binance.com{path}"))
+			.query(query)
+			.send()
+			.await?
+			.error_for_status()?
+			.text()
+			.await?)
+	})
+	.await
+}
+
+/// `(funding rate each perp will settle next, mark price)`, by Binance symbol (`BTCUSDT`); the rate as a fraction
+/// per funding interval.
+async fn premium_index(client: &reqwest::Client) -> Result<HashMap<String, (f64, f64)>> {
+	#[serde_as]
+	#[derive(Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	struct Row {
+		symbol: String,
+		#[serde_as(as = "DisplayFromStr")]
+		last_funding_rate: f64,
+		#[serde_as(as = "DisplayFromStr")]
+		mark_price: f64,
+	}
+	let body = binance(client, "premiumIndex", "/fapi/v1/premiumIndex", &[]).await?;
+	let rows: Vec<Row> = serde_json::from_str(&body).wrap_err("malformed premiumIndex response")?;
+	Ok(rows.into_iter().map(|r| (r.symbol, (r.last_funding_rate, r.mark_price))).collect())
+}
+
+/// Funding interval in hours, by symbol, of the perps that don't settle every [`DEFAULT_INTERVAL_HOURS`]; Binance
+/// only lists the ones whose interval or rate cap was adjusted.
+async fn funding_intervals(client: &reqwest::Client) -> Result<HashMap<String, u32>> {
+	#[derive(Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	struct Row {
+		symbol: String,
+		funding_interval_hours: u32,
+	}
+	let body = binance(client, "fundingInfo", "/fapi/v1/fundingInfo", &[]).await?;
+	let rows: Vec<Row> = serde_json::from_str(&body).wrap_err("malformed fundingInfo response")?;
+	Ok(rows.into_iter().filter(|r| r.funding_interval_hours > 0).map(|r| (r.symbol, r.funding_interval_hours)).collect())
+}
+
+/// Every funding settlement since `since_ms`, by symbol. Without a `symbol`, Binance returns all perps' rows in
+/// time order, a page at a time.
+async fn funding_since(client: &reqwest::Client, since_ms: i64) -> Result<HashMap<String, Vec<f64>>> {
+	#[serde_as]
+	#[derive(Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	struct Row {
+		symbol: String,
+		#[serde_as(as = "DisplayFromStr")]
+		funding_rate: f64,
+		funding_time: i64, // ms
+	}
+	let mut by_symbol: HashMap<String, Vec<f64>> = HashMap::new();
+	let mut start = since_ms;
+	for page in 0.. {
+		let (start_s, limit) = (start.to_string(), FUNDING_PAGE.to_string());
+		let body = binance(
+			client,
+			&format!("fundingRate/{page}"),
+			"/fapi/v1/fundingRate",
+			&[("startTime", start_s.as_str()), ("limit", limit.as_str())],
+		)
+		.await?;
+		let rows: Vec<Row> = serde_json::from_str(&body).wrap_err("malformed fundingRate response")?;
+		let full = rows.len() == FUNDING_PAGE;
+		let Some(last) = rows.iter().map(|r| r.funding_time).max() else { break };
+		for r in rows {
+			by_symbol.entry(r.symbol).or_default().push(r.funding_rate);
+		}
+		//NB: settlements sharing the page's last ms may straddle the page edge; we step past that ms rather than risk
+		// refetching the same page forever, and accept the rare miss
+		if !full {
+			break;
+		}
+		start = last + 1;
+	}
+	Ok(by_symbol)
+}
+
+/// Open interest of `symbol` right now, in contracts (units of the base asset).
+async fn open_interest(client: &reqwest::Client, symbol: &str) -> Result<f64> {
+	#[serde_as]
+	#[derive(Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	struct Response {
+		#[serde_as(as = "DisplayFromStr")]
+		open_interest: f64,
+	}
+	let body = binance(client, &format!("openInterest/{symbol}"), "/fapi/v1/openInterest", &[("symbol", symbol)]).await?;
+	let r: Response = serde_json::from_str(&body).wrap_err_with(|| format!("malformed openInterest response for {symbol}"))?;
+	Ok(r.open_interest)
+}
+
+/// `oi_usd` by pair off the persisted poll nearest a day back, if there's one within an hour of it; empty until
+/// history reaches that far.
+fn oi_day_ago() -> HashMap<String, f64> {
+	let target = jiff::Timestamp::now() - jiff::SignedDuration::from_hours(24);
+	let hour = jiff::SignedDuration::from_hours(1);
+	let polls = match load_history::<FundingOi>(target - hour..target + hour) {
+		Ok(polls) => polls,
+		Err(e) => {
+			warn!("Couldn't read funding history for the 24h OI change: {e:?}");
+			return HashMap::new();
+		}
+	};
+	polls
+		.into_iter()
+		.min_by_key(|s| (s.fetched_at.as_second() - target.as_second()).abs())
+		.map(|s| s.data.perps.into_iter().filter_map(|p| Some((p.pair, p.oi_usd?))).collect())
+		.unwrap_or_default()
+}
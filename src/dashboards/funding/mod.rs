@@ -0,0 +1,254 @@
+#[cfg(feature = "ssr")]
+mod data;
+
+use leptos::{html::*, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use super::{LoadingWithProgress, LoadingWithProgressProps};
+
+/// Rows in each outlier list.
+#[cfg_attr(not(feature = "ssr"), allow(dead_code))]
+const OUTLIERS: usize = 8;
+/// Perps with less open interest than this (USD) are left out of the OI lists: on a thin book a single position
+/// swings it by tens of %.
+#[cfg_attr(not(feature = "ssr"), allow(dead_code))]
+const MIN_OI_USD: f64 = 5_000_000.0;
+
+#[component]
+pub fn FundingView() -> impl IntoView {
+	let trigger = RwSignal::new(());
+	let funding_resource = Resource::new(move || trigger.get(), |_| async move { funding_outliers().await });
+
+	// Refetch the moment the server persists a fresh copy
+	#[cfg(feature = "hydrate")]
+	super::on_source_event("FundingOi", move |ev| {
+		if let super::SourceEvent::Refreshed { .. } = ev {
+			trigger.update(|_| ());
+		}
+	});
+
+	// Set up retry interval - retry every 1 minute on error
+	#[cfg(not(feature = "ssr"))]
+	{
+		Effect::new(move || {
+			if let Some(Err(_)) = funding_resource.get() {
+				set_timeout(
+					move || {
+						trigger.update(|_| ());
+					},
+					std::time::Duration::from_secs(60),
+				);
+			}
+		});
+	}
+
+	let loading = || {
+		LoadingWithProgress(LoadingWithProgressProps {
+			label: "Funding & OI".into(),
+			name: "FundingOi".into(),
+		})
+	};
+	div().class("panel-center").child(Suspense(SuspenseProps {
+		fallback: loading.into(),
+		children: ToChildren::to_children(move || {
+			IntoRender::into_render(move || match funding_resource.get() {
+				Some(Ok(outliers)) => outliers_view(&outliers).into_any(),
+				Some(Err(e)) => (pre().child(format!("Error loading funding and OI: {e} (retrying...)")),).into_any(),
+				None => (loading(),).into_any(),
+			})
+		}),
+	}))
+}
+
+/// Funding and open interest of every Binance USDT perp.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct FundingOi {
+	perps: Vec<PerpFunding>,
+	/// USDT perps listed, whether or not they made it into `perps`.
+	total_pairs: usize,
+}
+/// Rates are in % per 8h, scaled from the perp's own funding interval, so perps settling hourly rank alongside
+/// those settling every 8h.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct PerpFunding {
+	pair: String,
+	/// Hours between settlements: 8 on most, 4 or 1 on some.
+	#[serde(default = "eight")]
+	interval_hours: u32,
+	/// Rate the next settlement will pay.
+	funding: f64,
+	/// Mean of the settlements over the last 24h.
+	funding_24h: Option<f64>,
+	oi_usd: Option<f64>,
+	/// In %, against `oi_usd` in the poll a day back.
+	oi_change_24h: Option<f64>,
+}
+
+/// The extremes of a [`FundingOi`], each list most extreme first.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct FundingOutliers {
+	/// Open-interest-weighted mean of the current rates.
+	mean_funding: Option<f64>,
+	highest_funding: Vec<PerpFunding>,
+	lowest_funding: Vec<PerpFunding>,
+	oi_up: Vec<PerpFunding>,
+	oi_down: Vec<PerpFunding>,
+	loaded: usize,
+	total_pairs: usize,
+}
+#[cfg(feature = "ssr")]
+impl From<&FundingOi> for FundingOutliers {
+	fn from(f: &FundingOi) -> Self {
+		let top = |mut v: Vec<&PerpFunding>, key: fn(&PerpFunding) -> f64| {
+			v.sort_by(|a, b| key(b).total_cmp(&key(a)));
+			v.into_iter().take(OUTLIERS).cloned().collect::<Vec<_>>()
+		};
+		let all = || f.perps.iter().collect::<Vec<_>>();
+		let liquid = || {
+			f.perps
+				.iter()
+				.filter(|p| p.oi_usd.is_some_and(|oi| oi >= MIN_OI_USD) && p.oi_change_24h.is_some())
+				.collect::<Vec<_>>()
+		};
+
+		let weighted = f.perps.iter().filter_map(|p| Some((p.funding, p.oi_usd?)));
+		let (sum, weight) = weighted.fold((0.0, 0.0), |(s, w), (rate, oi)| (s + rate * oi, w + oi));
+		Self {
+			mean_funding: (weight > 0.0).then_some(sum / weight),
+			highest_funding: top(all(), |p| p.funding),
+			lowest_funding: top(all(), |p| -p.funding),
+			oi_up: top(liquid(), |p| p.oi_change_24h.unwrap_or_default()),
+			oi_down: top(liquid(), |p| -p.oi_change_24h.unwrap_or_default()),
+			loaded: f.perps.len(),
+			total_pairs: f.total_pairs,
+		}
+	}
+}
+
+fn outliers_view(o: &FundingOutliers) -> impl IntoView {
+	let list = |title: &str, perps: &[PerpFunding]| {
+		let rows = perps
+			.iter()
+			.map(|p| {
+				tr().child((
+					td().class("px-1 text-left").child((
+						p.pair.trim_end_matches("-USDT").to_owned(),
+						(p.interval_hours != 8).then(|| span().class("text-gray-500").child(format!(" {}h", p.interval_hours))),
+					)),
+					td().class("px-1 text-right")
+						.style(format!("color: {}", sign_color(p.funding)))
+						.child(format!("{:+.4}", p.funding)),
+					td().class("px-1 text-right").child(p.funding_24h.map_or_else(|| "–".to_owned(), |f| format!("{f:+.4}"))),
+					td().class("px-1 text-right").child(p.oi_usd.map_or_else(|| "–".to_owned(), usd)),
+					td().class("px-1 text-right")
+						.style(format!("color: {}", p.oi_change_24h.map_or("inherit", sign_color)))
+						.child(p.oi_change_24h.map_or_else(|| "–".to_owned(), |c| format!("{c:+.1}%"))),
+				))
+			})
+			.collect::<Vec<_>>();
+		div().child((
+			div().class("font-bold text-sm").child(title.to_owned()),
+			table().class("mx-auto text-xs font-mono").child((
+				thead().child(tr().child((
+					th().class("px-1"),
+					th().class("px-1 text-right").child("fund/8h"),
+					th().class("px-1 text-right").child("24h avg"),
+					th().class("px-1 text-right").child("OI"),
+					th().class("px-1 text-right").child("ΔOI 24h"),
+				))),
+				tbody().child(rows),
+			)),
+		))
+	};
+
+	let coverage = o.loaded as f64 / o.total_pairs.max(1) as f64;
+	div().class("w-full").child((
+		div().class("grid grid-cols-2 gap-2").child((
+			list("Highest funding", &o.highest_funding),
+			list("Lowest funding", &o.lowest_funding),
+			list("OI up", &o.oi_up),
+			list("OI down", &o.oi_down),
+		)),
+		div().class("mt-1 text-xs text-gray-500").child((
+			o.mean_funding.map(|m| format!("OI-weighted funding {m:+.4}%/8h · ")),
+			span()
+				.style(if coverage < 0.7 { "color: #d97706" } else { "" })
+				.child(format!("{}/{} Binance USDT perps", o.loaded, o.total_pairs)),
+		)),
+	))
+}
+
+fn eight() -> u32 {
+	8
+}
+
+fn sign_color(v: f64) -> &'static str {
+	if v > 0.0 {
+		"#16a34a"
+	} else if v < 0.0 {
+		"#dc2626"
+	} else {
+		"inherit"
+	}
+}
+
+fn usd(v: f64) -> String {
+	match v {
+		v if v >= 1e9 => format!("{:.1}B", v / 1e9),
+		v if v >= 1e6 => format!("{:.0}M", v / 1e6),
+		v => format!("{:.0}K", v / 1e3),
+	}
+}
+
+#[server]
+async fn funding_outliers() -> Result<FundingOutliers, ServerFnError> {
+	let funding = super::_core::load::<FundingOi>().await.map_err(|e| {
+		tracing::error!("Failed to load funding and open interest: {e:?}");
+		ServerFnError::new(format!("Failed to load funding and open interest: {e}"))
+	})?;
+	Ok((&funding.data).into())
+}
+#[cfg(feature = "ssr")]
+pub(super) fn register(scheduler: &mut super::_core::Scheduler) {
+	scheduler.register::<FundingOi>();
+}
+#[cfg(feature = "ssr")]
+impl super::_core::SourceData for FundingOi {
+	type Params = ();
+
+	fn decay_horizon() -> v_utils::trades::Timeframe {
+		"1h".into()
+	}
+
+	async fn fetch(_: &()) -> color_eyre::eyre::Result<Self> {
+		data::get().await
+	}
+
+	/// A spread of majors and alts, with a couple of crowded longs and a short squeeze in the making.
+	fn fixture(_: &()) -> Option<Self> {
+		let perp = |pair: &str, funding: f64, funding_24h: f64, oi_usd: f64, oi_change_24h: f64| PerpFunding {
+			pair: format!("{pair}-USDT"),
+			interval_hours: if pair == "NEIRO" { 4 } else { 8 },
+			funding,
+			funding_24h: Some(funding_24h),
+			oi_usd: Some(oi_usd),
+			oi_change_24h: Some(oi_change_24h),
+		};
+		Some(Self {
+			perps: vec![
+				perp("BTC", 0.0100, 0.0087, 9.8e9, 2.1),
+				perp("ETH", 0.0112, 0.0095, 6.1e9, 3.4),
+				perp("SOL", 0.0150, 0.0121, 2.3e9, 7.8),
+				perp("XRP", 0.0061, 0.0070, 1.1e9, -1.2),
+				perp("DOGE", 0.0243, 0.0188, 9.4e8, 11.5),
+				perp("PEPE", 0.0512, 0.0390, 2.2e8, 24.3),
+				perp("WIF", -0.0431, -0.0210, 1.8e8, 18.9),
+				perp("ORDI", -0.0125, -0.0098, 7.5e7, -9.6),
+				perp("ARB", 0.0035, 0.0041, 3.1e8, -4.3),
+				perp("TIA", -0.0062, -0.0015, 1.2e8, -12.7),
+				perp("NEIRO", 0.0950, 0.0610, 4.1e6, 62.0),
+			],
+			total_pairs: 12,
+		})
+	}
+}
@@ -0,0 +1,171 @@
+//! The liquidation book: every liquidation the exchange streams push, summed into [`BUCKET_SECS`] ×
+//! [`PRICE_STEP`] cells over the last [`WINDOW_SECS`]. It lives in memory, fed by the streams' tasks and read by
+//! each [`LiqHeatmap`] poll; [`seed`] fills it from the last persisted poll on startup, so a restart costs only
+//! the liquidations missed while down.
+use std::{collections::BTreeMap, sync::Mutex, time::Duration};
+
+use color_eyre::eyre::{Result, bail, eyre};
+use futures::{SinkExt as _, StreamExt as _};
+use serde::Deserialize;
+use serde_with::{DisplayFromStr, serde_as};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{info, warn};
+
+use super::{BUCKET_SECS, LiqCell, LiqHeatmap, PRICE_STEP, SYMBOL, WINDOW_SECS};
+
+/// A stream that's pushed no frame (pings included) for this long is taken as dead, and reconnected.
+const IDLE: Duration = Duration::from_secs(10 * 60);
+
+struct Book {
+	/// `(bucket start in UNIX seconds, price step index)` → `(longs, shorts)` liquidated, in USD.
+	cells: BTreeMap<(i64, i64), (f64, f64)>,
+	/// When the book started collecting; nothing before it was seen.
+	since: Option<i64>,
+}
+static BOOK: Mutex<Book> = Mutex::new(Book {
+	cells: BTreeMap::new(),
+	since: None,
+});
+
+/// Adds the last persisted poll's cells to the book. Reads the disk, so it's for startup, before the streams run;
+/// anything they've already recorded is kept alongside.
+pub fn seed() {
+	let since = jiff::Timestamp::now() - jiff::SignedDuration::from_secs(WINDOW_SECS);
+	let last = match crate::dashboards::_core::load_history::<LiqHeatmap>(since..) {
+		Ok(mut polls) => polls.pop(),
+		Err(e) => {
+			warn!("Couldn't read liquidation history to seed from: {e:?}");
+			None
+		}
+	};
+	let Some(last) = last else { return };
+	let mut book = BOOK.lock().unwrap();
+	for c in last.data.cells {
+		let cell = book.cells.entry((c.time, (c.price / PRICE_STEP).round() as i64)).or_default();
+		cell.0 += c.long_usd;
+		cell.1 += c.short_usd;
+	}
+	book.since = match (book.since, last.data.since) {
+		(Some(a), Some(b)) => Some(a.min(b)),
+		(a, b) => a.or(b),
+	};
+}
+
+/// One liquidation: `long` when a long position was closed out.
+fn record(time_ms: i64, price: f64, usd: f64, long: bool) {
+	let time = time_ms / 1000;
+	let key = (time - time.rem_euclid(BUCKET_SECS), (price / PRICE_STEP).floor() as i64);
+	let mut book = BOOK.lock().unwrap();
+	book.since.get_or_insert(time);
+	let cell = book.cells.entry(key).or_default();
+	if long {
+		cell.0 += usd;
+	} else {
+		cell.1 += usd;
+	}
+}
+
+/// Current cells, oldest first, with anything past the window dropped; and since when they've been collected.
+pub fn cells() -> (Vec<LiqCell>, Option<i64>) {
+	let cutoff = jiff::Timestamp::now().as_second() - WINDOW_SECS;
+	let mut book = BOOK.lock().unwrap();
+	book.cells.retain(|(time, _), _| *time >= cutoff - BUCKET_SECS);
+	let cells = book
+		.cells
+		.iter()
+		.map(|(&(time, step), &(long_usd, short_usd))| LiqCell {
+			time,
+			price: step as f64 * PRICE_STEP,
+			long_usd,
+			short_usd,
+		})
+		.collect();
+	(cells, book.since.map(|s| s.max(cutoff)))
+}
+
+/// Binance's per-symbol force-order stream. It pushes at most one liquidation per symbol a second (the largest),
+/// so small ones in a cascade go unseen.
+pub async fn binance() -> Result<()> {
+	#[derive(Deserialize)]
+	struct Event {
+		o: Order,
+	}
+	#[serde_as]
+	#[derive(Deserialize)]
+	struct Order {
+		/// Side of the liquidating order: `SELL` closes out a long.
+		#[serde(rename = "S")]
+		side: String,
+		/// Average fill price.
+		#[serde_as(as = "DisplayFromStr")]
+		ap: f64,
+		/// Filled quantity.
+		#[serde_as(as = "DisplayFromStr")]
+		z: f64,
+		#[serde(rename = "T")]
+		time: i64, // ms
+	}
+
+	let (mut ws, _) = connect_async(format!("wss://fstream.binance.com/ws/{}@forceOrder", SYMBOL.to_lowercase())).await?;
+	info!("Binance liquidation stream connected");
+	// Binance pings every few minutes even while nothing gets liquidated
+	while let Some(msg) = tokio::time::timeout(IDLE, ws.next()).await.map_err(|_| eyre!("Binance liquidation stream silent for {IDLE:?}"))? {
+		let Message::Text(text) = msg? else { continue };
+		match serde_json::from_str::<Event>(&text) {
+			Ok(Event { o }) => record(o.time, o.ap, o.ap * o.z, o.side == "SELL"),
+			Err(e) => warn!("Unparseable Binance force order `{text}`: {e}"),
+		}
+	}
+	Ok(())
+}
+
+/// Bybit's all-liquidations stream. It drops connections that go quiet, hence the pings.
+pub async fn bybit() -> Result<()> {
+	#[derive(Deserialize)]
+	struct Push {
+		topic: Option<String>,
+		#[serde(default)]
+		data: Vec<Liquidation>,
+	}
+	#[serde_as]
+	#[derive(Deserialize)]
+	struct Liquidation {
+		/// Side of the liquidated position.
+		#[serde(rename = "S")]
+		side: String,
+		#[serde_as(as = "DisplayFromStr")]
+		p: f64,
+		#[serde_as(as = "DisplayFromStr")]
+		v: f64,
+		#[serde(rename = "T")]
+		time: i64, // ms
+	}
+
+	let (mut ws, _) = connect_async("wss://stream.bybit.com/v5/public/linear").await?;
+	ws.send(Message::text(format!(r#"{{"op":"subscribe","args":["allLiquidation.{SYMBOL}"]}}"#))).await?;
+	info!("Bybit liquidation stream connected");
+	let mut ping = tokio::time::interval(Duration::from_secs(20));
+	// our own pings don't count: only what comes back does
+	let mut heard = tokio::time::Instant::now();
+	loop {
+		tokio::select! {
+			_ = ping.tick() => ws.send(Message::text(r#"{"op":"ping"}"#)).await?,
+			_ = tokio::time::sleep_until(heard + IDLE) => bail!("Bybit liquidation stream silent for {IDLE:?}"),
+			msg = ws.next() => {
+				heard = tokio::time::Instant::now();
+				let Some(msg) = msg else { return Ok(()) };
+				let Message::Text(text) = msg? else { continue };
+				match serde_json::from_str::<Push>(&text) {
+					// subscription acks and pongs carry no topic
+					Ok(Push { topic: None, .. }) => {}
+					Ok(Push { data, .. }) => {
+						for l in data {
+							record(l.time, l.p, l.p * l.v, l.side == "Buy");
+						}
+					}
+					Err(e) => warn!("Unparseable Bybit push `{text}`: {e}"),
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,223 @@
+//! BTC liquidations as a price × time heatmap. Binance's and Bybit's liquidation websockets feed an in-memory
+//! book through `_core` [streams](super::_core::Scheduler::stream); [`LiqHeatmap`] polls snapshot it, so the
+//! heatmap persists, histories and pushes refreshes like any other source. `lwc_liq.js` draws it.
+#[cfg(feature = "ssr")]
+mod data;
+
+use leptos::{html::*, prelude::*};
+use serde::{Deserialize, Serialize};
+
+/// Binance/Bybit symbol of the one perp tracked.
+#[cfg_attr(not(feature = "ssr"), allow(dead_code))]
+const SYMBOL: &str = "BTCUSDT";
+/// Width of a heatmap column.
+#[cfg_attr(not(feature = "ssr"), allow(dead_code))]
+const BUCKET_SECS: i64 = 15 * 60;
+/// Height of a heatmap row, in USD.
+//NB: absolute, so tuned to BTC's price; a second symbol would want it relative to price
+#[cfg_attr(not(feature = "ssr"), allow(dead_code))]
+const PRICE_STEP: f64 = 100.0;
+/// How far back the heatmap reaches.
+#[cfg_attr(not(feature = "ssr"), allow(dead_code))]
+const WINDOW_SECS: i64 = 24 * 3600;
+
+#[component]
+pub fn LiquidationsView() -> impl IntoView {
+	let loading = RwSignal::new(true);
+	let banner = RwSignal::new(None::<String>);
+	#[cfg(feature = "hydrate")]
+	{
+		use wasm_bindgen::JsCast;
+		use wasm_bindgen_futures::spawn_local;
+
+		#[derive(serde::Deserialize)]
+		struct StaleField {
+			stale: Option<String>,
+		}
+
+		let mount_chart = move || {
+			if let Some(el) = document().get_element_by_id("liq-chart") {
+				let el: web_sys::HtmlElement = el.dyn_into().unwrap();
+				spawn_local(async move {
+					match gloo_net::http::Request::get("/data/liquidations.json").send().await {
+						Ok(resp) if !resp.ok() => {
+							let body = resp.text().await.unwrap_or_default();
+							banner.set(Some(format!("⚠ Liquidations unavailable — {}", body.trim())));
+						}
+						Ok(resp) => match resp.text().await {
+							Ok(body) => {
+								let stale = serde_json::from_str::<StaleField>(&body).ok().and_then(|s| s.stale);
+								banner.set(v_utils::lwc::mount(el, "/lwc_liq.js", &body, "null").await.or(stale));
+							}
+							Err(e) => banner.set(Some(format!("⚠ Liquidations unavailable — {e}"))),
+						},
+						Err(e) => banner.set(Some(format!("⚠ Liquidations unavailable — {e}"))),
+					}
+					loading.set(false);
+				});
+			}
+		};
+		Effect::new(move |_| mount_chart());
+		// the draw module swaps data into the mounted chart in place, so a refresh needs no loading overlay
+		super::on_source_event("LiqHeatmap", move |ev| {
+			if let super::SourceEvent::Refreshed { .. } = ev {
+				mount_chart();
+			}
+		});
+	}
+
+	div().style("height:100%;display:flex;flex-direction:column").child((
+		move || {
+			banner.get().map(|msg| {
+				div()
+					.class("shrink-0 bg-amber-500/15 text-amber-200 text-[11px] leading-snug px-2 py-1 border-b border-amber-500/30")
+					.child(msg)
+			})
+		},
+		div()
+			.style("flex:1 1 auto;min-height:0;position:relative")
+			.child((div().id("liq-chart").style("height:100%"), move || {
+				loading.get().then(|| {
+					div()
+						.class("absolute inset-0 flex items-center justify-center")
+						.child(super::LoadingIndicator(super::LoadingIndicatorProps { label: "Liquidations".into() }))
+				})
+			})),
+	))
+}
+
+/// Liquidations of [`SYMBOL`] over the last [`WINDOW_SECS`], binned, with the price path they happened along.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct LiqHeatmap {
+	/// E.g. `BTC-USDT`.
+	title: String,
+	bucket_secs: i64,
+	price_step: f64,
+	/// `BUCKET_SECS` closes, as `lwc_draw.js`'s lines are: parallel `time` (UNIX seconds) and `value`.
+	price: PriceLine,
+	/// Only cells something was liquidated in, oldest first.
+	cells: Vec<LiqCell>,
+	/// UNIX seconds since which liquidations were being collected; the heatmap is blank before it.
+	since: Option<i64>,
+}
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct PriceLine {
+	time: Vec<i64>,
+	value: Vec<f64>,
+}
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct LiqCell {
+	/// Column start, UNIX seconds.
+	time: i64,
+	/// Row's lower edge.
+	price: f64,
+	long_usd: f64,
+	short_usd: f64,
+}
+
+/// `/data/liquidations.json`: the [`LiqHeatmap`], plus `stale` when the served copy is past its grace window.
+#[cfg(feature = "ssr")]
+pub async fn liquidations_json_handler() -> axum::response::Response {
+	use axum::{http::StatusCode, response::IntoResponse};
+
+	match super::_core::load::<LiqHeatmap>().await {
+		Ok(loaded) => {
+			#[derive(serde::Serialize)]
+			struct Resp {
+				#[serde(flatten)]
+				heatmap: LiqHeatmap,
+				#[serde(skip_serializing_if = "Option::is_none")]
+				stale: Option<String>,
+			}
+			let stale = loaded.stale.map(|s| {
+				format!(
+					"⚠ Price feed unavailable — liquidations as of {} may be outdated. {}",
+					s.fetched_at.strftime("%Y-%m-%d %H:%M UTC"),
+					s.error
+				)
+			});
+			axum::Json(Resp { heatmap: loaded.data, stale }).into_response()
+		}
+		Err(e) => {
+			tracing::error!("Failed to load liquidation heatmap: {e:?}");
+			(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+		}
+	}
+}
+
+#[cfg(feature = "ssr")]
+pub(super) fn register(scheduler: &mut super::_core::Scheduler) {
+	data::seed();
+	scheduler
+		.register::<LiqHeatmap>()
+		.stream("liquidations-binance", data::binance)
+		.stream("liquidations-bybit", data::bybit);
+}
+#[cfg(feature = "ssr")]
+impl super::_core::SourceData for LiqHeatmap {
+	type Params = ();
+
+	fn decay_horizon() -> v_utils::trades::Timeframe {
+		"5m".into()
+	}
+
+	/// Only read back to seed the book on startup, and a poll older than the window has nothing left to seed.
+	fn history_retention() -> Option<jiff::SignedDuration> {
+		Some(jiff::SignedDuration::from_secs(WINDOW_SECS))
+	}
+
+	/// Snapshots the book the streams fill; only the price line goes upstream.
+	async fn fetch(_: &()) -> color_eyre::eyre::Result<Self> {
+		let bars = (WINDOW_SECS / BUCKET_SECS) as u32 + 1;
+		let (time, value) = super::market_structure::perp_klines(SYMBOL, "15m", bars).await?.into_iter().unzip();
+		let (cells, since) = data::cells();
+		Ok(Self {
+			title: "BTC-USDT".to_owned(),
+			bucket_secs: BUCKET_SECS,
+			price_step: PRICE_STEP,
+			price: PriceLine { time, value },
+			cells,
+			since,
+		})
+	}
+
+	/// A slow drift down and a flush, with longs liquidated on the way down and shorts caught on the bounce.
+	fn fixture(_: &()) -> Option<Self> {
+		const END: i64 = 1_760_000_400;
+		let n = WINDOW_SECS / BUCKET_SECS;
+		let close = |i: i64| 112_000.0 - 12.0 * i as f64 - if i > 70 { 900.0 - 25.0 * (i - 70) as f64 } else { 0.0 };
+		let time = |i: i64| END - (n - i) * BUCKET_SECS;
+		let mut cells = Vec::new();
+		for i in 0..n {
+			let (c, prev) = (close(i), close(i.max(1) - 1));
+			let row = |p: f64| (p / PRICE_STEP).floor() * PRICE_STEP;
+			// every bar shakes out a little of both; a big drop takes out longs below the old close
+			let drop = (prev - c).max(0.0);
+			cells.push(LiqCell {
+				time: time(i),
+				price: row(c),
+				long_usd: 40_000.0 + drop * 2_000.0,
+				short_usd: if i > 71 { 60_000.0 } else { 25_000.0 },
+			});
+			if drop > 200.0 {
+				cells.push(LiqCell {
+					time: time(i),
+					price: row(c) + PRICE_STEP,
+					long_usd: drop * 8_000.0,
+					short_usd: 0.0,
+				});
+			}
+		}
+		Some(Self {
+			title: "BTC-USDT".to_owned(),
+			bucket_secs: BUCKET_SECS,
+			price_step: PRICE_STEP,
+			price: PriceLine {
+				time: (0..n).map(time).collect(),
+				value: (0..n).map(close).collect(),
+			},
+			cells,
+			since: Some(time(0)),
+		})
+	}
+}
@@ -0,0 +1,506 @@
+//! Threshold alerts on % longs, per user. Rules are checked against every fresh copy of the Binance
+//! [`SortedLsrs`](super::data::SortedLsrs) the scheduler persists, and delivered by email or webhook.
+use leptos::{ev, html::*, prelude::*};
+use serde::{Deserialize, Serialize};
+
+/// Per user; each is checked against every pair it covers on each refresh.
+#[cfg_attr(not(feature = "ssr"), allow(dead_code))]
+const MAX_RULES: usize = 20;
+/// Lookbacks offered for [`Condition::ChangesBy`]; all fit in the 24h of bars `SortedLsrs` keeps.
+const WINDOWS: [(u32, &str); 4] = [(15, "15m"), (60, "1h"), (240, "4h"), (1440, "24h")];
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Condition {
+	/// % longs moves across `level`, either way.
+	Crosses { level: f64 },
+	/// % longs moves by more than `pp` percentage points over the last `window_mins`.
+	ChangesBy { pp: f64, window_mins: u32 },
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Delivery {
+	/// To the account's address.
+	Email,
+	/// JSON `POST` of `{rule, text, alerts: [{pair, message}]}`.
+	Webhook { url: String },
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AlertRule {
+	pub id: String,
+	/// Base symbol; `None` watches every pair.
+	pub pair: Option<String>,
+	pub condition: Condition,
+	pub delivery: Delivery,
+}
+impl AlertRule {
+	fn describe(&self) -> String {
+		let who = self.pair.as_deref().unwrap_or("Any pair");
+		let what = match self.condition {
+			Condition::Crosses { level } => format!("% longs crosses {level}"),
+			Condition::ChangesBy { pp, window_mins } => format!("% longs moves >{pp}pp in {}", window_label(window_mins)),
+		};
+		let via = match &self.delivery {
+			Delivery::Email => "email".to_owned(),
+			Delivery::Webhook { url } => format!("webhook {url}"),
+		};
+		format!("{who}: {what} → {via}")
+	}
+}
+
+fn window_label(mins: u32) -> String {
+	WINDOWS.iter().find(|w| w.0 == mins).map_or_else(|| format!("{mins}m"), |w| w.1.to_owned())
+}
+
+#[component]
+pub fn AlertRules() -> impl IntoView {
+	let trigger = RwSignal::new(());
+	let saved = Resource::new(move || trigger.get(), |_| list_alert_rules());
+	let kind = RwSignal::new("crosses".to_owned());
+	let pair = RwSignal::new(String::new());
+	let value = RwSignal::new(String::new());
+	let window_mins = RwSignal::new(60u32);
+	let via = RwSignal::new("email".to_owned());
+	let url = RwSignal::new(String::new());
+	let status = RwSignal::new(None::<String>);
+
+	let add = move |_| {
+		let Ok(v) = value.get_untracked().trim().parse::<f64>() else {
+			status.set(Some("Threshold must be a number".into()));
+			return;
+		};
+		let webhook = (via.get_untracked() == "webhook").then(|| url.get_untracked().trim().to_owned());
+		let pair = Some(pair.get_untracked().trim().to_uppercase()).filter(|p| !p.is_empty());
+		let (kind, window_mins) = (kind.get_untracked(), window_mins.get_untracked());
+		leptos::task::spawn_local(async move {
+			match create_alert_rule(pair, kind, v, window_mins, webhook).await {
+				Ok(()) => {
+					status.set(None);
+					value.set(String::new());
+					trigger.update(|_| ());
+				}
+				Err(e) => status.set(Some(e.to_string())),
+			}
+		});
+	};
+
+	move || {
+		// `None` for anonymous visitors — alerts need an account to deliver to
+		let Some(Ok(Some(rules))) = saved.get() else { return None };
+		Some(
+			div().class("mt-4 text-sm space-y-1").child((
+				div().class("font-semibold").child("Alerts"),
+				rules
+					.into_iter()
+					.map(|rule| {
+						let id = rule.id.clone();
+						div().class("flex items-center justify-between").child((
+							span().child(rule.describe()),
+							button()
+								.class("px-2 border rounded")
+								.attr("type", "button")
+								.on(ev::click, move |_| {
+									let id = id.clone();
+									leptos::task::spawn_local(async move {
+										match delete_alert_rule(id).await {
+											Ok(()) => trigger.update(|_| ()),
+											Err(e) => status.set(Some(e.to_string())),
+										}
+									});
+								})
+								.child("✕"),
+						))
+					})
+					.collect::<Vec<_>>(),
+				div().class("flex flex-wrap items-center gap-2").child((
+					input()
+						.class("p-1 border rounded w-20")
+						.attr("type", "text")
+						.attr("placeholder", "Any pair")
+						.prop("value", pair)
+						.on(ev::input, move |ev| pair.set(event_target_value(&ev))),
+					select().class("p-1 border rounded").on(ev::change, move |ev| kind.set(event_target_value(&ev))).child((
+						option().value("crosses").selected(kind.get_untracked() == "crosses").child("% longs crosses"),
+						option().value("changes_by").selected(kind.get_untracked() == "changes_by").child("% longs moves by (pp)"),
+					)),
+					input()
+						.class("p-1 border rounded w-16")
+						.attr("type", "text")
+						.attr("placeholder", move || if kind.get() == "crosses" { "75" } else { "5" })
+						.prop("value", value)
+						.on(ev::input, move |ev| value.set(event_target_value(&ev))),
+					move || {
+						(kind.get() == "changes_by").then(|| {
+							select()
+								.class("p-1 border rounded")
+								.on(ev::change, move |ev| {
+									if let Ok(m) = event_target_value(&ev).parse() {
+										window_mins.set(m);
+									}
+								})
+								.child(
+									WINDOWS
+										.iter()
+										.map(|(m, label)| option().value(m.to_string()).selected(window_mins.get_untracked() == *m).child(format!("in {label}")))
+										.collect::<Vec<_>>(),
+								)
+						})
+					},
+					select().class("p-1 border rounded").on(ev::change, move |ev| via.set(event_target_value(&ev))).child((
+						option().value("email").selected(via.get_untracked() == "email").child("by email"),
+						option().value("webhook").selected(via.get_untracked() == "webhook").child("to webhook"),
+					)),
+					move || {
+						(via.get() == "webhook").then(|| {
+							input()
+								.class("p-1 border rounded")
+								.attr("type", "url")
+								.attr("placeholder", "https://…")
+								.prop("value", url)
+								.on(ev::input, move |ev| url.set(event_target_value(&ev)))
+						})
+					},
+					button().class("px-2 py-1 border rounded").attr("type", "button").on(ev::click, add).child("Add alert"),
+				)),
+				move || status.get().map(|s| div().class("text-gray-500").child(s)),
+			)),
+		)
+	}
+}
+
+/// `None` when nobody is logged in.
+#[server]
+async fn list_alert_rules() -> Result<Option<Vec<AlertRule>>, ServerFnError> {
+	let Some(user) = crate::app::server_impl::get_current_user_impl().await? else {
+		return Ok(None);
+	};
+	let rows = db()?.list_lsr_alert_rules(Some(&user.id)).await.map_err(|e| ServerFnError::new(format!("DB error: {e}")))?;
+	Ok(Some(rows.into_iter().filter_map(|r| server::rule_from_row(r).ok()).collect()))
+}
+
+/// `kind` is `crosses` (`value` a % longs level) or `changes_by` (`value` in pp over `window_mins`).
+/// Delivered to `webhook` if given, else by email.
+#[server]
+async fn create_alert_rule(pair: Option<String>, kind: String, value: f64, window_mins: u32, webhook: Option<String>) -> Result<(), ServerFnError> {
+	let user = crate::app::server_impl::get_current_user_impl().await?.ok_or_else(|| ServerFnError::new("Not logged in"))?;
+
+	if let Some(bad) = pair.as_ref().filter(|b| b.len() > 20 || !b.chars().all(|c| c.is_ascii_alphanumeric())) {
+		return Err(ServerFnError::new(format!("Not a symbol: {bad}")));
+	}
+	let condition = match kind.as_str() {
+		"crosses" if (0.0..=100.0).contains(&value) => Condition::Crosses { level: value },
+		"crosses" => return Err(ServerFnError::new("Level is a % of longs, 0–100")),
+		"changes_by" if !(value > 0.0 && value <= 100.0) => return Err(ServerFnError::new("Change is in percentage points, above 0 and up to 100")),
+		"changes_by" if !WINDOWS.iter().any(|w| w.0 == window_mins) => return Err(ServerFnError::new("Unsupported window")),
+		"changes_by" => Condition::ChangesBy { pp: value, window_mins },
+		_ => return Err(ServerFnError::new(format!("Unknown condition {kind}"))),
+	};
+	let db = db()?;
+	let delivery = match webhook {
+		Some(url) if url.len() <= 512 => {
+			server::webhook_target(&url).await.map_err(|e| ServerFnError::new(format!("Webhook refused: {e}")))?;
+			Delivery::Webhook { url }
+		}
+		Some(_) => return Err(ServerFnError::new("Webhook URL is too long")),
+		None => {
+			let verified = db.is_email_verified(&user.id).await.map_err(|e| ServerFnError::new(format!("DB error: {e}")))?;
+			if !verified {
+				return Err(ServerFnError::new("Verify your email address to get alerts by email"));
+			}
+			Delivery::Email
+		}
+	};
+
+	let existing = db.list_lsr_alert_rules(Some(&user.id)).await.map_err(|e| ServerFnError::new(format!("DB error: {e}")))?;
+	if existing.len() >= MAX_RULES {
+		return Err(ServerFnError::new(format!("At most {MAX_RULES} alerts per account")));
+	}
+	let id = uuid::Uuid::new_v4().to_string();
+	let condition = serde_json::to_string(&condition).expect("serializes");
+	let delivery = serde_json::to_string(&delivery).expect("serializes");
+	db.create_lsr_alert_rule(&id, &user.id, pair.as_deref().unwrap_or_default(), &condition, &delivery)
+		.await
+		.map_err(|e| ServerFnError::new(format!("DB error: {e}")))?;
+	Ok(())
+}
+
+#[server]
+async fn delete_alert_rule(id: String) -> Result<(), ServerFnError> {
+	let user = crate::app::server_impl::get_current_user_impl().await?.ok_or_else(|| ServerFnError::new("Not logged in"))?;
+	db()?.delete_lsr_alert_rule(&user.id, &id).await.map_err(|e| ServerFnError::new(format!("DB error: {e}")))?;
+	Ok(())
+}
+
+#[cfg(feature = "ssr")]
+fn db() -> Result<crate::auth::Database, ServerFnError> {
+	use_context::<crate::auth::Database>().ok_or_else(|| ServerFnError::new("Database not available"))
+}
+
+#[cfg(feature = "ssr")]
+pub use server::spawn;
+#[cfg(feature = "ssr")]
+mod server {
+	use std::{
+		collections::HashMap,
+		net::{IpAddr, Ipv4Addr, SocketAddr},
+	};
+
+	use color_eyre::eyre::{Result, WrapErr as _, bail, eyre};
+	use jiff::{SignedDuration, Timestamp};
+	use tokio::sync::broadcast::error::RecvError;
+	use v_exchanges::Lsrs;
+	use v_utils::trades::Pair;
+
+	use super::{AlertRule, Condition, Delivery};
+	use crate::{
+		auth::{Database, EmailSender, LsrAlertRuleRow},
+		config::LiveSettings,
+		dashboards::{
+			_core, SourceEvent,
+			lsr::data::{LsrVenue, SortedLsrs},
+		},
+	};
+
+	/// `SortedLsrs` is polled as 5m bars.
+	const BAR_MINS: u32 = 5;
+	/// A rule stays quiet on a pair for this long after firing on it, so a level hovering at the threshold
+	/// doesn't mail on every refresh.
+	const COOLDOWN: SignedDuration = SignedDuration::from_hours(1);
+
+	/// Checks every rule each time the scheduler persists a fresh Binance `SortedLsrs`.
+	pub fn spawn(settings: LiveSettings, db: Database) {
+		tokio::spawn(async move {
+			let source = _core::id::<SortedLsrs>(&LsrVenue::Binance);
+			let mut rx = _core::subscribe();
+			let mut fired: HashMap<(String, Pair), Timestamp> = HashMap::new();
+			loop {
+				match rx.recv().await {
+					Ok(SourceEvent::Refreshed { source: s, .. }) if s == source =>
+						if let Err(e) = evaluate(&settings, &db, &mut fired).await {
+							tracing::warn!("LSR alert evaluation failed: {e:?}");
+						},
+					Ok(_) => {}
+					// missed ticks are fine: the next refresh is checked in full
+					Err(RecvError::Lagged(n)) => tracing::debug!("LSR alerts lagged, skipped {n} events"),
+					Err(RecvError::Closed) => return,
+				}
+			}
+		});
+	}
+
+	async fn evaluate(settings: &LiveSettings, db: &Database, fired: &mut HashMap<(String, Pair), Timestamp>) -> Result<()> {
+		let rows = db.list_lsr_alert_rules(None).await?;
+		if rows.is_empty() {
+			return Ok(());
+		}
+		let lsrs = _core::with_settings(settings, _core::load_with::<SortedLsrs>(&LsrVenue::Binance)).await?.data;
+		let now = Timestamp::now();
+		fired.retain(|_, at| now.duration_since(*at) < COOLDOWN);
+
+		for row in rows {
+			let (email, verified) = (row.user_email.clone(), row.user_email_verified);
+			let rule = match rule_from_row(row) {
+				Ok(r) => r,
+				Err(e) => {
+					tracing::warn!("skipping malformed LSR alert rule: {e:?}");
+					continue;
+				}
+			};
+			// only an address its owner has proven is theirs gets mail; an unverified one could be anyone's
+			if rule.delivery == Delivery::Email && !verified {
+				continue;
+			}
+			let mut hits = Vec::new();
+			for lsr in lsrs.iter().filter(|l| rule.pair.as_ref().is_none_or(|b| l.pair.base().to_string() == *b)) {
+				let key = (rule.id.clone(), lsr.pair);
+				if fired.contains_key(&key) {
+					continue;
+				}
+				if let Some(msg) = check(rule.condition, lsr) {
+					fired.insert(key, now);
+					hits.push((lsr.pair, msg));
+				}
+			}
+			if hits.is_empty() {
+				continue;
+			}
+			if let Err(e) = deliver(settings, &rule, &email, &hits).await {
+				tracing::warn!("failed to deliver LSR alert {}: {e:?}", rule.id);
+			}
+		}
+		Ok(())
+	}
+
+	/// Message for `lsr` if `condition` holds on its latest bar.
+	fn check(condition: Condition, lsr: &Lsrs) -> Option<String> {
+		let longs: Vec<f64> = lsr.iter().map(|l| l.long() * 100.0).collect();
+		let cur = *longs.last()?;
+		match condition {
+			Condition::Crosses { level } => {
+				let prev = longs[..longs.len() - 1].last().copied()?;
+				let dir = if prev < level && cur >= level {
+					"above"
+				} else if prev >= level && cur < level {
+					"below"
+				} else {
+					return None;
+				};
+				Some(format!("% longs crossed {dir} {level}: {prev:.2} → {cur:.2}"))
+			}
+			Condition::ChangesBy { pp, window_mins } => {
+				let back = (window_mins / BAR_MINS) as usize;
+				let then = *longs.get(longs.len().checked_sub(back + 1)?)?;
+				let delta = cur - then;
+				(delta.abs() > pp).then(|| format!("% longs moved {delta:+.2}pp in {}: {then:.2} → {cur:.2}", super::window_label(window_mins)))
+			}
+		}
+	}
+
+	async fn deliver(settings: &LiveSettings, rule: &AlertRule, email: &str, hits: &[(Pair, String)]) -> Result<()> {
+		let lines: Vec<String> = hits.iter().map(|(pair, msg)| format!("{pair}: {msg}")).collect();
+		let text = format!("{}\n\n{}", rule.describe(), lines.join("\n"));
+		match &rule.delivery {
+			Delivery::Email => {
+				let smtp = settings.config()?.smtp;
+				if smtp.username.is_empty() {
+					bail!("SMTP is not configured");
+				}
+				let subject = match hits {
+					[(pair, _)] => format!("LSR alert: {pair}"),
+					_ => format!("LSR alert: {} pairs", hits.len()),
+				};
+				EmailSender::try_new(&smtp)?.send(email, &subject, text).await
+			}
+			Delivery::Webhook { url } => {
+				// vetted again on every send and pinned to the vetted addresses, so a DNS change since the rule
+				// was saved can't swing it inward; redirects aren't followed for the same reason
+				let (host, addrs) = webhook_target(url).await?;
+				let client = reqwest::Client::builder()
+					.redirect(reqwest::redirect::Policy::none())
+					.resolve_to_addrs(&host, &addrs)
+					.timeout(std::time::Duration::from_secs(10))
+					.build()?;
+				let alerts: Vec<_> = hits.iter().map(|(pair, msg)| serde_json::json!({ "pair": pair.to_string(), "message": msg })).collect();
+				let response = client
+					.post(url)
+					.json(&serde_json::json!({ "rule": rule.id, "text": text, "alerts": alerts }))
+					.send()
+					.await?
+					.error_for_status()
+					.wrap_err("webhook rejected the alert")?;
+				if response.status().is_redirection() {
+					bail!("webhook answered {} instead of accepting the alert", response.status());
+				}
+				Ok(())
+			}
+		}
+	}
+
+	/// Host of the webhook at `url` and what it resolves to, refused unless it's https and every address is
+	/// publicly routable — so a rule can't aim the server at itself, its network or the cloud metadata service.
+	pub(super) async fn webhook_target(url: &str) -> Result<(String, Vec<SocketAddr>)> {
+		let parsed = reqwest::Url::parse(url).wrap_err("not a URL")?;
+		if parsed.scheme() != "https" {
+			bail!("must be an https:// URL");
+		}
+		let host = parsed.host_str().ok_or_else(|| eyre!("URL has no host"))?.to_owned();
+		let port = parsed.port_or_known_default().unwrap_or(443);
+		let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.trim_start_matches('[').trim_end_matches(']'), port))
+			.await
+			.wrap_err_with(|| format!("can't resolve {host}"))?
+			.collect();
+		if addrs.is_empty() {
+			bail!("{host} resolves to no address");
+		}
+		if let Some(internal) = addrs.iter().find(|a| is_internal(a.ip())) {
+			bail!("{host} resolves to non-public address {}", internal.ip());
+		}
+		Ok((host, addrs))
+	}
+
+	/// Loopback, private, link-local, CGNAT, unspecified, broadcast or multicast. A v6 address carrying a v4 one
+	/// (mapped, IPv4-compatible, 6to4 or NAT64) counts as that v4 address, since that's where it gets routed.
+	fn is_internal(ip: IpAddr) -> bool {
+		match ip {
+			IpAddr::V4(v4) => {
+				let [a, b, ..] = v4.octets();
+				v4.is_loopback() || v4.is_private() || v4.is_link_local() || v4.is_unspecified() || v4.is_broadcast() || v4.is_multicast() || a == 0 || (a == 100 && (64..128).contains(&b))
+			}
+			IpAddr::V6(v6) => {
+				let v4 = |hi: u16, lo: u16| Ipv4Addr::from((u32::from(hi) << 16) | u32::from(lo));
+				let embedded = match v6.segments() {
+					// 2002::/16 6to4: the gateway's v4 in bits 16-48
+					[0x2002, hi, lo, ..] => Some(v4(hi, lo)),
+					// 64:ff9b::/96 NAT64: the v4 in the last 32 bits
+					[0x64, 0xff9b, 0, 0, 0, 0, hi, lo] => Some(v4(hi, lo)),
+					// ::ffff:a.b.c.d mapped and ::a.b.c.d compatible; `::` and `::1` land in 0.0.0.0/8
+					_ => v6.to_ipv4(),
+				};
+				match embedded {
+					Some(v4) => is_internal(IpAddr::V4(v4)),
+					None => {
+						let first = v6.segments()[0];
+						// fc00::/7 unique local, fe80::/10 link-local
+						v6.is_loopback() || v6.is_unspecified() || v6.is_multicast() || first & 0xfe00 == 0xfc00 || first & 0xffc0 == 0xfe80
+					}
+				}
+			}
+		}
+	}
+
+	pub(super) fn rule_from_row(r: LsrAlertRuleRow) -> Result<AlertRule> {
+		Ok(AlertRule {
+			condition: serde_json::from_str(&r.condition).wrap_err_with(|| format!("condition of {}", r.id))?,
+			delivery: serde_json::from_str(&r.delivery).wrap_err_with(|| format!("delivery of {}", r.id))?,
+			pair: Some(r.pair).filter(|p| !p.is_empty()),
+			id: r.id,
+		})
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use super::*;
+
+		#[test]
+		fn internal_addresses() {
+			let cases = [
+				("8.8.8.8", false),
+				("127.0.0.1", true),
+				("10.1.2.3", true),
+				("172.16.0.1", true),
+				("192.168.1.1", true),
+				("169.254.169.254", true),
+				("100.64.0.1", true),
+				("100.128.0.1", false),
+				("0.1.2.3", true),
+				("255.255.255.255", true),
+				("224.0.0.1", true),
+				("2606:4700::1111", false),
+				("::1", true),
+				("::", true),
+				("fd00::1", true),
+				("fe80::1", true),
+				("ff02::1", true),
+				// mapped
+				("::ffff:127.0.0.1", true),
+				("::ffff:8.8.8.8", false),
+				// IPv4-compatible
+				("::10.0.0.1", true),
+				("::8.8.8.8", false),
+				// 6to4
+				("2002:a9fe:a9fe::1", true),
+				("2002:7f00:1::", true),
+				("2002:808:808::1", false),
+				// NAT64
+				("64:ff9b::a00:1", true),
+				("64:ff9b::a9fe:a9fe", true),
+				("64:ff9b::808:808", false),
+			];
+			for (ip, internal) in cases {
+				assert_eq!(is_internal(ip.parse().unwrap()), internal, "{ip}");
+			}
+		}
+	}
+}
@@ -1,32 +1,87 @@
 use std::{
-	fmt::Write as _,
+	collections::{BTreeMap, HashMap, HashSet},
 	sync::{Arc, Mutex},
 };
 
-use color_eyre::eyre::{Result, bail};
-use futures::future::join_all;
+use color_eyre::eyre::{Result, WrapErr as _, bail};
+use futures::{
+	future::join_all,
+	stream::{self, StreamExt as _},
+};
 use serde::{Deserialize, Serialize};
-use tracing::{info, instrument, warn};
+use serde_with::{DisplayFromStr, serde_as};
+use tracing::{debug, info, instrument, warn};
 use v_exchanges::{Lsrs, prelude::*};
-use v_utils::{trades::Timeframe, xdg_data_file};
+use v_utils::trades::{Pair, Timeframe};
+
+use crate::dashboards::_core::{SourceParams, cassette};
+
+/// Bar timeframe of every [`SortedLsrs`] series.
+pub const TF: &str = "5m";
+/// How far back each series reaches.
+pub const SPAN: &str = "24h";
+
+/// An exchange that publishes long/short account ratios. Each is its own [`SortedLsrs`] variant.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum LsrVenue {
+	#[default]
+	Binance,
+	Bybit,
+}
+impl LsrVenue {
+	pub const ALL: [Self; 2] = [Self::Binance, Self::Bybit];
+
+	fn exchange(self) -> ExchangeName {
+		match self {
+			Self::Binance => ExchangeName::Binance,
+			Self::Bybit => ExchangeName::Bybit,
+		}
+	}
+
+	/// Venue and instrument, e.g. `Binance/Perp`.
+	pub fn market(self) -> String {
+		format!("{self}/{INSTRUMENT:?}")
+	}
+
+	fn slug(self) -> &'static str {
+		match self {
+			Self::Binance => "binance",
+			Self::Bybit => "bybit",
+		}
+	}
+}
+impl std::fmt::Display for LsrVenue {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{:?}", self)
+	}
+}
+impl SourceParams for LsrVenue {
+	fn key(&self) -> String {
+		self.slug().to_owned()
+	}
+}
 
 #[instrument]
-pub async fn get(tf: Timeframe, range: RequestRange) -> Result<SortedLsrs> {
-	let mut bn = Binance::default();
-	bn.set_retry_config(RetryConfig {
+pub async fn get(venue: LsrVenue, tf: Timeframe, range: RequestRange) -> Result<SortedLsrs> {
+	let mut ex = venue.exchange().init_client();
+	ex.set_retry_config(RetryConfig {
 		max_retries: 3,
 		..Default::default()
 	});
-	bn.set_timeout(std::time::Duration::from_secs(60));
+	ex.set_timeout(std::time::Duration::from_secs(60));
 
-	let pairs = bn.exchange_info(INSTRUMENT).await?.usdt_pairs().collect::<Vec<_>>();
+	let slug = venue.slug();
+	let pairs: Vec<Pair> = cassette::tape(&format!("{slug}/{INSTRUMENT}/usdt_pairs"), async {
+		Ok(ex.exchange_info(INSTRUMENT).await?.usdt_pairs().collect())
+	})
+	.await?;
 	let pairs_len = pairs.len();
 
-	let lsr_no_data_pairs_file = xdg_data_file!("lsr_no_data_pairs.txt");
+	let lsr_no_data_pairs_file = v_utils::xdg_data_dir!("lsr").join(format!("no_data_pairs_{slug}.txt"));
 	let lsr_no_data_pairs = match std::fs::metadata(&lsr_no_data_pairs_file) {
 		Ok(metadata) => {
 			let age = metadata.modified().unwrap().elapsed().unwrap();
-			// Binance could start supporting any of the ignored pairs, so we refetch once a month
+			// the exchange could start supporting any of the ignored pairs, so we refetch once a month
 			if age < std::time::Duration::from_hours(30 * 24) {
 				std::fs::read_to_string(&lsr_no_data_pairs_file)
 					.map(|s| s.lines().filter(|s| !s.is_empty()).map(|s| s.into()).collect())
@@ -39,22 +94,23 @@ pub async fn get(tf: Timeframe, range: RequestRange) -> Result<SortedLsrs> {
 	};
 	let lsr_pairs = pairs.into_iter().filter(|p| !lsr_no_data_pairs.contains(&p.to_string())).collect::<Vec<_>>();
 
-	let progress = crate::dashboards::_core::Progress::of::<SortedLsrs>();
+	let progress = crate::dashboards::_core::Progress::of::<SortedLsrs>(&venue);
 	progress.set_target(lsr_pairs.len());
 	let new_no_data_pairs = Arc::new(Mutex::new(Vec::new()));
 	let handles = lsr_pairs.iter().map(|p| {
 		let new_no_data_pairs = Arc::clone(&new_no_data_pairs);
-		let bn = Arc::new(&bn);
+		let ex = &ex;
 		async move {
-			let r = match bn.lsr(*p, tf, range, "Global".into()).await {
+			let key = format!("{slug}/lsr/{p}/{}s/{range:?}/Global", tf.duration().as_secs());
+			let r = match cassette::tape(&key, async { Ok(ex.lsr(*p, tf, range, "Global".into()).await?) }).await {
 				Ok(lsr_vec) if !lsr_vec.is_empty() => Some(lsr_vec),
 				Ok(_) => {
-					info!("No data for {p}");
+					info!("No data for {p} on {venue}");
 					new_no_data_pairs.lock().unwrap().push(p.to_string());
 					None
 				}
 				Err(e) => {
-					warn!("Couldn't fetch data for {p}: {e:?}");
+					warn!("Couldn't fetch data for {p} on {venue}: {e:?}");
 					None
 				}
 			};
@@ -70,8 +126,19 @@ pub async fn get(tf: Timeframe, range: RequestRange) -> Result<SortedLsrs> {
 	}
 
 	let lsrs: Vec<Lsrs> = results.into_iter().flatten().collect();
+	let success_rate = (lsrs.len() as f64 / pairs_len as f64) * 100.0;
+	if success_rate < 70.0 {
+		warn!("{venue} LSR data: {}/{pairs_len} pairs loaded ({success_rate:.1}% - below 70% threshold)", lsrs.len());
+	} else {
+		info!("{venue} LSR data: {}/{pairs_len} pairs loaded ({success_rate:.1}%)", lsrs.len());
+	}
 	let mut sorted_lsrs = SortedLsrs::build(lsrs);
 	sorted_lsrs.__total_pairs_on_exchange = Some(pairs_len);
+	// only Binance publishes the top-trader ratios
+	if venue == LsrVenue::Binance {
+		let pairs: Vec<Pair> = sorted_lsrs.iter().map(|l| l.pair).collect();
+		top_up_months(&pairs).await;
+	}
 
 	Ok(sorted_lsrs)
 }
@@ -85,59 +152,259 @@ pub struct SortedLsrs {
 }
 impl SortedLsrs {
 	pub fn build(mut v: Vec<Lsrs>) -> Self {
-		v.sort_by(|a, b| a.last().unwrap().long().partial_cmp(&b.last().unwrap().long()).unwrap());
+		v.sort_by(|a, b| a.last().unwrap().long().total_cmp(&b.last().unwrap().long()));
 		Self { v, ..Default::default() }
 	}
+}
 
-	fn display_most_shorted_longed_row(&self, i: usize) -> Result<String> {
-		if self.len() < 2 * i {
-			bail!("Not enough data");
-		}
-		let shorted_str = self.get(i).expect("checked earlier").display_change()?;
-		let longed_str = self.get(self.len() - i - 1).expect("checked earlier").display_change()?;
-		Ok(format!("{shorted_str}{longed_str}"))
-	}
-
-	pub fn display_outliers(&self, rows: usize) -> String {
-		let mut s = String::new();
-		let display_rows_ceiling = std::cmp::min(rows, self.len() / 2 /*floor*/);
-		for i in 0..display_rows_ceiling {
-			if i == 0 {
-				let title = |t: &'static str| format!("{:<width$}", format!("Most {t} (% longs)"), width = Lsrs::CHANGE_STR_LEN);
-				s.write_fmt(format_args!("{}{}", title("Shorted"), title("Longed"))).unwrap();
-				// match formatting of `fmt_lsr` (when counting, don't forget all symbols outside of main paddings)
-			}
-			s.push('\n');
+/// Pairs listed on both, widest gap in latest share long first, as `(pair, a, b)`; capped at `rows`, alongside
+/// how many pairs the two share.
+pub fn divergence(a: &SortedLsrs, b: &SortedLsrs, rows: usize) -> (Vec<(Pair, f64, f64)>, usize) {
+	let mut gaps: Vec<(Pair, f64, f64)> = a
+		.iter()
+		.filter_map(|x| {
+			let y = b.iter().find(|y| y.pair == x.pair)?;
+			Some((x.pair, x.last()?.long(), y.last()?.long()))
+		})
+		.collect();
+	let shared = gaps.len();
+	gaps.sort_by(|x, y| (y.1 - y.2).abs().total_cmp(&(x.1 - x.2).abs()));
+	gaps.truncate(rows);
+	(gaps, shared)
+}
+
+/// The last [`MONTH_SECS`] of one pair's [`Ratio`]s at [`MONTH_PERIOD`], in [`Ratio::ALL`] order; each series
+/// `(UNIX seconds, share long)`, ascending. A ratio Binance has no data for is left out. Binance only.
+///
+/// Its own source, one variant per pair, rather than a field of [`SortedLsrs`]: the 5m readers of that never want
+/// the months, and a chart wants one pair's. The Binance [`SortedLsrs`] poll tops them all up as a batch.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct RatioMonth {
+	pub pair: Pair,
+	pub series: Vec<(Ratio, Vec<(i64, f64)>)>,
+}
+#[derive(Clone, Debug, Default)]
+pub struct MonthParams {
+	/// E.g. `BTC-USDT`.
+	pub pair: String,
+}
+impl SourceParams for MonthParams {
+	fn key(&self) -> String {
+		self.pair.clone()
+	}
+}
+impl RatioMonth {
+	fn get(&self, ratio: Ratio) -> Option<&[(i64, f64)]> {
+		self.series.iter().find(|s| s.0 == ratio).map(|s| s.1.as_slice())
+	}
+
+	/// `points` on top of what `ratio` already has, the fresh copy winning where the two overlap.
+	fn merge(&mut self, ratio: Ratio, points: Vec<(i64, f64)>) {
+		let mut by_time: BTreeMap<i64, f64> = self.get(ratio).unwrap_or_default().iter().copied().collect();
+		by_time.extend(points);
+		self.series.retain(|s| s.0 != ratio);
+		self.series.push((ratio, by_time.into_iter().collect()));
+		self.series.sort_by_key(|s| Ratio::ALL.iter().position(|r| *r == s.0));
+	}
+
+	fn trim(&mut self, since: i64) {
+		self.series.iter_mut().for_each(|(_, points)| points.retain(|p| p.0 >= since));
+		self.series.retain(|s| !s.1.is_empty());
+	}
+}
+
+pub const MONTH_PERIOD: &str = "4h";
+/// Binance keeps no more than this of any ratio.
+const MONTH_SECS: i64 = 30 * 24 * 3600;
+/// Ratio calls one poll may spend on [`RatioMonth`]s. The ratio endpoints share a 1000-per-5m IP budget, and the
+/// 24h Global fetch above already spends one call per pair of it every poll; so the first fill of ~3 calls per pair
+/// is spread over a few polls, after which each poll only tops up the series a new point has come out for.
+const MONTH_CALLS_PER_POLL: usize = 300;
+/// Ratio calls in flight at once.
+const MONTH_CONCURRENCY: usize = 10;
 
-			s.push_str(
-				&self
-					.display_most_shorted_longed_row(i)
-					.expect("guaranteed enough elements (iterating over `self.len() / 2` minimum)"),
-			);
+/// Series of `month` a newer point is out for, as `(ratio, last point held)`.
+fn due_series(month: Option<&RatioMonth>, now: i64, step: i64) -> Vec<(Ratio, Option<i64>)> {
+	Ratio::ALL
+		.into_iter()
+		.map(|ratio| (ratio, month.and_then(|m| m.get(ratio)?.last()).map(|p| p.0)))
+		.filter(|(_, last)| last.is_none_or(|t| now - t >= step))
+		.collect()
+}
+
+/// Fetches what each of `due` is missing since its last point, [`MONTH_CONCURRENCY`] at a time.
+async fn fetch_due(due: Vec<(Pair, Ratio, Option<i64>)>, now: i64) -> Vec<(Pair, Ratio, Result<Vec<(i64, f64)>>)> {
+	let step = Timeframe::from(MONTH_PERIOD).duration().as_secs() as i64;
+	let full = (MONTH_SECS / step) as u32;
+	stream::iter(due.into_iter().map(|(pair, ratio, last)| async move {
+		let limit = last.map_or(full, |t| ((now - t) / step + 1).min(full as i64) as u32);
+		(pair, ratio, binance_ratio(ratio, pair, MONTH_PERIOD, limit).await)
+	}))
+	.buffer_unordered(MONTH_CONCURRENCY)
+	.collect()
+	.await
+}
+/// Brings the persisted [`RatioMonth`] of each of `pairs` up to date, furthest behind first and within
+/// [`MONTH_CALLS_PER_POLL`], persisting each one touched.
+async fn top_up_months(pairs: &[Pair]) {
+	let now = jiff::Timestamp::now().as_second();
+	let step = Timeframe::from(MONTH_PERIOD).duration().as_secs() as i64;
+	let params = |pair: &Pair| MonthParams { pair: pair.to_string() };
+	let mut months: HashMap<Pair, RatioMonth> = pairs
+		.iter()
+		.filter_map(|pair| Some((*pair, crate::dashboards::_core::persisted_with::<RatioMonth>(&params(pair))?)))
+		.collect();
+
+	// the ones furthest behind (or never fetched) first
+	let mut due: Vec<(Pair, Ratio, Option<i64>)> = pairs
+		.iter()
+		.flat_map(|&pair| due_series(months.get(&pair), now, step).into_iter().map(move |(ratio, last)| (pair, ratio, last)))
+		.collect();
+	due.sort_by_key(|d| d.2.unwrap_or(i64::MIN));
+	if due.len() > MONTH_CALLS_PER_POLL {
+		info!("{} ratio series due, topping up the {MONTH_CALLS_PER_POLL} furthest behind this poll", due.len());
+		due.truncate(MONTH_CALLS_PER_POLL);
+	}
+
+	let mut touched = HashSet::new();
+	for (pair, ratio, r) in fetch_due(due, now).await {
+		match r {
+			Ok(points) => {
+				months.entry(pair).or_insert_with(|| RatioMonth { pair, series: Vec::new() }).merge(ratio, points);
+				touched.insert(pair);
+			}
+			// the top-trader series lag listings by a while; a new pair can have Global alone
+			Err(e) => debug!("No {ratio:?} ratio for {pair}: {e:?}"),
 		}
-		s.push_str(&format!("\n{:-^width$}", "", width = Lsrs::CHANGE_STR_LEN));
-		s.push_str(&format!("\nAverage: {:.2}", self.iter().map(|lsr| lsr.last().unwrap().long()).sum::<f64>() / self.len() as f64));
-
-		let total_pairs = self.__total_pairs_on_exchange.expect("A dumb unwrap, really should be an error");
-		let success_rate = (self.len() as f64 / total_pairs as f64) * 100.0;
-
-		// Color only the numerator based on success rate
-		let pairs_info = if success_rate < 70.0 {
-			warn!("LSR data: {}/{total_pairs} pairs loaded ({success_rate:.1}% - below 70% threshold)", self.len());
-			format!(
-				"\nCollected for <span style=\"color: #f59e0b;\">{}</span>/{total_pairs} pairs on Binance/{INSTRUMENT:?}",
-				self.len()
-			)
-		} else {
-			info!("LSR data: {}/{total_pairs} pairs loaded ({success_rate:.1}%)", self.len());
-			format!("\nCollected for {}/{total_pairs} pairs on Binance/{INSTRUMENT:?}", self.len())
-		};
-		s.push_str(&pairs_info);
+	}
+	for pair in touched {
+		let Some(month) = months.get_mut(&pair) else { continue };
+		month.trim(now - MONTH_SECS);
+		if let Err(e) = crate::dashboards::_core::persist_with(&params(&pair), &*month).await {
+			warn!("Couldn't persist the {pair} ratio month: {e:?}");
+		}
+	}
+}
 
-		s
+/// One pair's month on its own, topped up from its persisted copy; for a chart asking before the batch got to it.
+pub async fn month(pair: Pair) -> Result<RatioMonth> {
+	let now = jiff::Timestamp::now().as_second();
+	let step = Timeframe::from(MONTH_PERIOD).duration().as_secs() as i64;
+	let mut month = crate::dashboards::_core::persisted_with::<RatioMonth>(&MonthParams { pair: pair.to_string() }).unwrap_or(RatioMonth { pair, series: Vec::new() });
+	let due = due_series(Some(&month), now, step).into_iter().map(|(ratio, last)| (pair, ratio, last)).collect();
+	for (_, ratio, r) in fetch_due(due, now).await {
+		match r {
+			Ok(points) => month.merge(ratio, points),
+			Err(e) => debug!("No {ratio:?} ratio for {pair}: {e:?}"),
+		}
+	}
+	month.trim(now - MONTH_SECS);
+	if month.series.is_empty() {
+		bail!("Binance has no long/short ratios for {pair}");
 	}
+	Ok(month)
 }
 
 static INSTRUMENT: Instrument = Instrument::Perp;
 
-//Q: potentially fix to "1D", req and store full month of data for both Global and Top Positions, to display when searching for specific one.
+/// Which of Binance's long/short series. All three report the share held long; they differ in who is counted.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Ratio {
+	/// Every account with a position.
+	Global,
+	/// Top 20% of accounts by margin balance, counted per account.
+	TopAccounts,
+	/// Same top 20%, weighted by position size.
+	TopPositions,
+}
+impl Ratio {
+	pub const ALL: [Self; 3] = [Self::Global, Self::TopAccounts, Self::TopPositions];
+
+	pub fn label(self) -> &'static str {
+		match self {
+			Self::Global => "Global accounts",
+			Self::TopAccounts => "Top accounts",
+			Self::TopPositions => "Top positions",
+		}
+	}
+
+	fn endpoint(self) -> &'static str {
+		match self {
+			Self::Global => "globalLongShortAccountRatio",
+			Self::TopAccounts => "topLongShortAccountRatio",
+			Self::TopPositions => "topLongShortPositionRatio",
+		}
+	}
+}
+
+/// `(UNIX seconds, share long 0…1)` of one [`Ratio`] for a USDT perp, ascending: the last `limit` points, paged
+/// back 500 at a time (Binance's cap per call). Periods below are the ones Binance serves; it only keeps the last
+/// 30 days, so a long lookback on a short period comes back with fewer points than asked for.
+pub async fn binance_ratio(ratio: Ratio, pair: Pair, period: &str, limit: u32) -> Result<Vec<(i64, f64)>> {
+	const PAGE: u32 = 500;
+	if !["5m", "15m", "30m", "1h", "2h", "4h", "6h", "12h", "1d"].contains(&period) {
+		bail!("Binance has no {period} ratio period");
+	}
+	let symbol = pair.to_string().replace('-', "");
+	let endpoint = ratio.endpoint();
+	let mut points: Vec<(i64, f64)> = Vec::new();
+	let mut end_ms = None;
+	while (points.len() as u32) < limit {
+		let want = (limit - points.len() as u32).min(PAGE);
+		let page = match ratio_page(endpoint, &symbol, period, want, end_ms).await {
+			Ok(page) => page,
+			// reaching past what Binance keeps can error rather than come back short
+			Err(e) if !points.is_empty() => {
+				debug!("stopped paging {endpoint} for {symbol} at {} points: {e:?}", points.len());
+				break;
+			}
+			Err(e) => return Err(e),
+		};
+		let exhausted = (page.len() as u32) < want;
+		let Some(&(oldest_ms, _)) = page.first() else { break };
+		end_ms = Some(oldest_ms - 1);
+		points.splice(0..0, page.into_iter().map(|(ms, long)| (ms / 1000, long)));
+		if exhausted {
+			break;
+		}
+	}
+	if points.is_empty() {
+		bail!("empty {endpoint} series for {symbol}");
+	}
+	Ok(points)
+}
+
+/// Up to `limit` `(ms, share long)` ending at `end_ms`, or at the latest point; ascending.
+async fn ratio_page(endpoint: &str, symbol: &str, period: &str, limit: u32, end_ms: Option<i64>) -> Result<Vec<(i64, f64)>> {
+	let limit = limit.to_string();
+	let mut key = format!("binance/{endpoint}/{symbol}/{period}/{limit}");
+	let mut query = vec![("symbol", symbol.to_owned()), ("period", period.to_owned()), ("limit", limit)];
+	if let Some(end_ms) = end_ms {
+		key.push_str(&format!("/end={end_ms}"));
+		query.push(("endTime", end_ms.to_string()));
+	}
+	let body = cassette::http(&key, async {
+		Ok(reqwest::Client::new()
+			.get(format!("https://fapi.binance.com/futures/data/{endpoint}"))
+			.query(&query)
+			.send()
+			.await?
+			.error_for_status()?
+			.text()
+			.await?)
+	})
+	.await?;
+
+	// the position ratio reports its shares under the same `longAccount` name
+	#[serde_as]
+	#[derive(Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	struct Row {
+		#[serde_as(as = "DisplayFromStr")]
+		long_account: f64,
+		timestamp: i64, // ms
+	}
+	let mut rows: Vec<Row> = serde_json::from_str(&body).wrap_err_with(|| format!("malformed {endpoint} response"))?;
+	rows.sort_by_key(|r| r.timestamp);
+	Ok(rows.into_iter().map(|r| (r.timestamp, r.long_account)).collect())
+}
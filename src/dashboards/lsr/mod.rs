@@ -1,5 +1,12 @@
+mod alerts;
 #[cfg(feature = "ssr")]
 mod data;
+mod table;
+mod watchlist;
+#[cfg(feature = "ssr")]
+pub use alerts::spawn as spawn_alerts;
+#[cfg(feature = "ssr")]
+pub(super) use data::{Ratio, binance_ratio};
 use leptos::{
 	control_flow::{ForEnumerate, ForEnumerateProps},
 	ev,
@@ -10,24 +17,25 @@ use serde::{Deserialize, Serialize};
 use v_utils::trades::Pair;
 use web_sys::wasm_bindgen::JsValue;
 
-use super::{LoadingWithProgress, LoadingWithProgressProps};
+use super::{LoadingIndicator, LoadingIndicatorProps, LoadingWithProgress, LoadingWithProgressProps};
 
 /// Main wrapper component that fetches LSR data and passes it to both search and display
 #[component]
 pub fn LsrView() -> impl IntoView {
 	let trigger = RwSignal::new(());
-	let lsrs_resource = Resource::new(move || trigger.get(), |_| async move { build_lsrs().await });
+	let lsrs_resource = Resource::new(move || trigger.get(), |_| async move { lsr_snapshot().await });
 
-	// Set up interval to refresh data every 5 minutes (client-side only)
+	// Refetch the moment the server persists a fresh copy (client-side only)
 	#[cfg(feature = "hydrate")]
 	{
-		use gloo_timers::callback::Interval;
-		use send_wrapper::SendWrapper;
-
-		let interval = SendWrapper::new(Interval::new(5 * 60 * 1000, move || {
-			trigger.update(|_| ());
-		}));
-		on_cleanup(move || drop(interval));
+		super::on_source_event_where(
+			|source| source.starts_with("SortedLsrs-"),
+			move |ev| {
+				if let super::SourceEvent::Refreshed { .. } = ev {
+					trigger.update(|_| ());
+				}
+			},
+		);
 
 		// Retry every 1 minute on error
 		Effect::new(move || {
@@ -47,19 +55,22 @@ pub fn LsrView() -> impl IntoView {
 			|| {
 				LoadingWithProgress(LoadingWithProgressProps {
 					label: "LSR".into(),
-					name: "SortedLsrs".into(),
+					name: "SortedLsrs-binance".into(),
 				})
 			}
 		}
 		.into(),
 		children: ToChildren::to_children(move || {
 			IntoRender::into_render(move || match lsrs_resource.get() {
-				Some(Ok(rendered_lsrs)) => {
-					let outliers = rendered_lsrs.outliers.clone();
-					let lsrs_vec = rendered_lsrs.v.clone();
+				Some(Ok(snapshot)) => {
+					let rows = snapshot.rows.clone();
 					(div().child((
-						pre().inner_html(outliers),
-						LsrSearchAndDisplayIsland(LsrSearchAndDisplayIslandProps { rendered_lsrs: lsrs_vec.clone() }),
+						table::LsrTable(table::LsrTableProps { snapshot: snapshot.clone() }),
+						table::DivergenceTable(table::DivergenceTableProps {
+							divergence: snapshot.divergence.clone(),
+						}),
+						LsrSearchAndDisplayIsland(LsrSearchAndDisplayIslandProps { rows }),
+						alerts::AlertRules(),
 					)),)
 						.into_any()
 				}
@@ -67,7 +78,7 @@ pub fn LsrView() -> impl IntoView {
 				None => (
 					LoadingWithProgress(LoadingWithProgressProps {
 						label: "LSR".into(),
-						name: "SortedLsrs".into(),
+						name: "SortedLsrs-binance".into(),
 					}),
 					().into_any(),
 				)
@@ -78,89 +89,145 @@ pub fn LsrView() -> impl IntoView {
 }
 
 #[component]
-pub fn LsrSearchAndDisplayIsland(rendered_lsrs: Vec<RenderedLsr>) -> impl IntoView {
-	let rendered_lsrs_memo = Memo::new(move |_| rendered_lsrs.clone());
+pub fn LsrSearchAndDisplayIsland(rows: Vec<LsrRow>) -> impl IntoView {
+	let rows = Memo::new(move |_| rows.clone());
 
 	// Signal to track selected pairs from URL
 	let selected_pairs = RwSignal::new(Vec::<Pair>::new());
 
-	// Read URL params on mount and update selected_pairs
+	// Read URL params on mount and update selected_pairs: `?lsr=BTC,ETH` directly, `?wl=<code>` via the shared watchlist
 	#[cfg(not(feature = "ssr"))]
 	Effect::new(move |_| {
 		if let Some(window) = web_sys::window() {
 			if let Ok(search) = window.location().search() {
-				if let Some(lsr_start) = search.find("lsr=") {
-					let after_lsr = &search[lsr_start + 4..];
-					let end = after_lsr.find('&').unwrap_or(after_lsr.len());
-					let lsr_str = &after_lsr[..end];
-					let pairs: Vec<Pair> = lsr_str
-						.split(',')
-						.filter_map(|s| {
-							let s = s.trim().to_uppercase();
-							let with_suffix = if s.ends_with("-USDT") || s.ends_with("USDT") { s } else { format!("{s}-USDT") };
-							with_suffix.parse::<Pair>().ok()
-						})
-						.collect();
-					selected_pairs.set(pairs);
+				if let Some(lsr_str) = query_param(&search, "lsr") {
+					selected_pairs.set(parse_pairs(lsr_str.split(',')));
+				} else if let Some(code) = query_param(&search, "wl") {
+					let code = code.to_owned();
+					leptos::task::spawn_local(async move {
+						match watchlist::shared_watchlist(code).await {
+							Ok(Some(w)) => selected_pairs.set(w.pairs()),
+							Ok(None) => leptos::logging::warn!("no watchlist is shared under that link"),
+							Err(e) => leptos::logging::error!("failed to load shared watchlist: {e}"),
+						}
+					});
 				}
 			}
 		}
 	});
 
 	(
-		LsrSearch(LsrSearchProps {
-			rendered_lsrs: rendered_lsrs_memo,
-			selected_pairs,
-		}),
-		LsrDisplay(LsrDisplayProps {
-			rendered_lsrs: rendered_lsrs_memo,
-			selected_pairs,
-		}),
+		LsrSearch(LsrSearchProps { rows, selected_pairs }),
+		watchlist::WatchlistBar(watchlist::WatchlistBarProps { selected_pairs }),
+		LsrDisplay(LsrDisplayProps { rows, selected_pairs }),
 	)
 }
 
-#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize, derive_new::new)]
-pub struct RenderedLsr {
+/// Value of `key` in a `?a=1&b=2` location search string.
+#[cfg_attr(feature = "ssr", allow(dead_code))] // URL state is only read client-side
+fn query_param<'a>(search: &'a str, key: &str) -> Option<&'a str> {
+	search.trim_start_matches('?').split('&').find_map(|kv| kv.strip_prefix(key)?.strip_prefix('='))
+}
+
+/// `BTC`, `btcusdt` or `BTC-USDT` as USDT pairs; entries that don't parse are dropped.
+fn parse_pairs<'a>(symbols: impl IntoIterator<Item = &'a str>) -> Vec<Pair> {
+	symbols
+		.into_iter()
+		.filter_map(|s| {
+			let s = s.trim().to_uppercase();
+			let with_suffix = if s.ends_with("-USDT") || s.ends_with("USDT") { s } else { format!("{s}-USDT") };
+			with_suffix.parse::<Pair>().ok()
+		})
+		.collect()
+}
+
+/// Reflect the selection in the URL as `?lsr=BTC,ETH` (omitting the -USDT suffix for cleaner URLs).
+fn push_selection_url(pairs: &[Pair]) {
+	if let Some(window) = web_sys::window() {
+		if let Ok(history) = window.history() {
+			let new_url = if pairs.is_empty() {
+				"/dashboards".to_string()
+			} else {
+				let lsr_param = pairs.iter().map(|p| p.base().to_string()).collect::<Vec<_>>().join(",");
+				format!("/dashboards?lsr={lsr_param}")
+			};
+			let _ = history.push_state_with_url(&JsValue::NULL, "", Some(&new_url));
+		}
+	}
+}
+
+/// One pair's positioning, as of the latest bar.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct LsrRow {
+	pub pair: Pair,
+	/// % of accounts long.
+	pub long: f64,
+	/// Change in `long` over the snapshot's `span`, in percentage points.
+	pub change: f64,
+}
+impl LsrRow {
+	fn summary(&self) -> String {
+		format!("{:.2}% ({:+.2}pp)", self.long, self.change)
+	}
+}
+
+/// Everything the LSR panel shows, typed; what [`lsr_snapshot`] serves.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct LsrSnapshot {
+	/// Ascending by `long`.
+	pub rows: Vec<LsrRow>,
+	/// Where `rows` are from, e.g. `Binance/Perp`.
+	pub venue: String,
+	/// Bar timeframe of the underlying series.
+	pub tf: String,
+	/// What `change` is measured over.
+	pub span: String,
+	/// Pairs with data, out of all listed on `venue`.
+	pub collected: usize,
+	pub listed: usize,
+	/// Pairs whose positioning differs most between venues, widest gap first; `Err` says why there's none.
+	pub divergence: Result<Divergence, String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Divergence {
+	pub venues: (String, String),
+	/// `(pair, % long on the first venue, % long on the second)`.
+	pub rows: Vec<(Pair, f64, f64)>,
+	/// Pairs with data on both.
+	pub shared: usize,
+}
+
+/// One pair's long/short ratios over the last month, as the selected row of [`LsrDisplay`] charts them.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct LsrHistory {
 	pub pair: Pair,
-	pub rend: String,
-	/// The LSR value (% longs) for sorting
-	pub value: f64,
+	/// Global accounts, then the top-trader ratios Binance has for the pair.
+	pub series: Vec<RatioSeries>,
 }
-#[derive(Clone, Debug, Default, derive_more::Deref, Deserialize, PartialEq, Serialize)]
-pub struct RenderedLsrs {
-	#[deref]
-	pub v: Vec<RenderedLsr>,
-	pub outliers: String,
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct RatioSeries {
+	pub label: String,
+	pub color: String,
+	pub time: Vec<i64>, // UNIX seconds
+	pub long: Vec<f64>, // share of the counted side that's long, 0…1
 }
 #[component]
-fn LsrDisplay(rendered_lsrs: Memo<Vec<RenderedLsr>>, selected_pairs: RwSignal<Vec<Pair>>) -> impl IntoView {
+fn LsrDisplay(rows: Memo<Vec<LsrRow>>, selected_pairs: RwSignal<Vec<Pair>>) -> impl IntoView {
 	let selected_lsrs = Memo::new(move |_| {
 		let pairs = selected_pairs.get();
-		let all_lsrs = rendered_lsrs.get();
+		let all_lsrs = rows.get();
 
 		let mut lsrs: Vec<_> = pairs.into_iter().filter_map(|pair| all_lsrs.iter().find(|lsr| lsr.pair == pair).cloned()).collect();
-		lsrs.sort_by(|a, b| a.value.partial_cmp(&b.value).unwrap_or(std::cmp::Ordering::Equal));
+		lsrs.sort_by(|a, b| a.long.partial_cmp(&b.long).unwrap_or(std::cmp::Ordering::Equal));
 		lsrs
 	});
 
-	// Track which item is selected for deletion (None = no selection)
+	// Track which item is selected: its ratio history is charted, and Backspace/Delete removes it (None = no selection)
 	let selected_index: RwSignal<Option<usize>> = RwSignal::new(None);
 
 	// Helper to update URL after removing a pair
-	let update_url = move || {
-		if let Some(window) = web_sys::window() {
-			let pairs = selected_pairs.get();
-			if let Ok(history) = window.history() {
-				let new_url = if pairs.is_empty() {
-					"/dashboards".to_string()
-				} else {
-					let lsr_param = pairs.iter().map(|p| p.base().to_string()).collect::<Vec<_>>().join(",");
-					format!("/dashboards?lsr={lsr_param}")
-				};
-				let _ = history.push_state_with_url(&JsValue::NULL, "", Some(&new_url));
-			}
-		}
-	};
+	let update_url = move || push_selection_url(&selected_pairs.get());
 
 	// Handle deletion of selected item
 	let handle_delete = move || {
@@ -203,11 +270,11 @@ fn LsrDisplay(rendered_lsrs: Memo<Vec<RenderedLsr>>, selected_pairs: RwSignal<Ve
 	div().class("mt-4 space-y-2").child(ForEnumerate(ForEnumerateProps {
 		each: move || selected_lsrs.get(),
 		key: |item| item.pair,
-		children: move |i: ReadSignal<usize>, item: RenderedLsr| {
+		children: move |i: ReadSignal<usize>, item: LsrRow| {
 			let is_selected = move || selected_index.get() == Some(*i.read());
 			div()
 				.class(move || {
-					let base = "flex items-center justify-between p-2 rounded cursor-pointer";
+					let base = "p-2 rounded cursor-pointer";
 					if is_selected() {
 						format!("{base} bg-blue-100")
 					} else {
@@ -218,22 +285,108 @@ fn LsrDisplay(rendered_lsrs: Memo<Vec<RenderedLsr>>, selected_pairs: RwSignal<Ve
 					let idx = *i.read_untracked();
 					move |_| handle_click(idx)
 				})
-				.child(span().child(item.rend.clone()))
+				.child((
+					div()
+						.class("flex items-center justify-between")
+						.child((span().class("font-semibold").child(item.pair.base().to_string()), span().child(item.summary()))),
+					move || is_selected().then(|| LsrHistoryChart(LsrHistoryChartProps { pair: item.pair })),
+				))
 		},
 	}))
 }
 
 #[component]
-fn LsrSearch(rendered_lsrs: Memo<Vec<RenderedLsr>>, selected_pairs: RwSignal<Vec<Pair>>) -> impl IntoView {
+fn LsrHistoryChart(pair: Pair) -> impl IntoView {
+	let history = Resource::new(move || pair, lsr_history);
+	div().class("mt-2").child(Suspense(SuspenseProps {
+		fallback: (move || LoadingIndicator(LoadingIndicatorProps { label: pair.to_string() })).into(),
+		children: ToChildren::to_children(move || {
+			IntoRender::into_render(move || match history.get() {
+				Some(Ok(h)) => ratio_chart(&h).into_any(),
+				Some(Err(e)) => pre().class("text-xs").child(format!("No ratio history for {pair}: {e}")).into_any(),
+				None => ().into_any(),
+			})
+		}),
+	}))
+}
+
+/// Inline SVG of each series on a shared % long axis, with the 50% line when it's in range.
+fn ratio_chart(h: &LsrHistory) -> impl IntoView {
+	const W: f64 = 300.0;
+	const H: f64 = 80.0;
+	let points = || h.series.iter().flat_map(|s| s.time.iter().zip(&s.long));
+	let (t0, t1) = points().fold((i64::MAX, i64::MIN), |(lo, hi), (t, _)| (lo.min(*t), hi.max(*t)));
+	let (lo, hi) = points().fold((f64::MAX, f64::MIN), |(lo, hi), (_, v)| (lo.min(*v), hi.max(*v)));
+	let (lo, hi) = (lo - 0.01, hi + 0.01);
+	let x = |t: i64| if t1 > t0 { (t - t0) as f64 / (t1 - t0) as f64 * W } else { W };
+	let y = |v: f64| (hi - v) / (hi - lo) * H;
+
+	let lines = h
+		.series
+		.iter()
+		.map(|s| {
+			let pts = s.time.iter().zip(&s.long).map(|(t, v)| format!("{:.1},{:.1}", x(*t), y(*v))).collect::<Vec<_>>().join(" ");
+			leptos::svg::polyline()
+				.attr("points", pts)
+				.attr("fill", "none")
+				.attr("stroke", s.color.clone())
+				.attr("stroke-width", "1.5")
+				.attr("vector-effect", "non-scaling-stroke")
+		})
+		.collect::<Vec<_>>();
+	let even = (lo < 0.5 && 0.5 < hi).then(|| {
+		leptos::svg::line()
+			.attr("x1", "0")
+			.attr("x2", W.to_string())
+			.attr("y1", y(0.5).to_string())
+			.attr("y2", y(0.5).to_string())
+			.attr("stroke", "#94a3b8")
+			.attr("stroke-dasharray", "3 3")
+			.attr("vector-effect", "non-scaling-stroke")
+	});
+	let legend = h
+		.series
+		.iter()
+		.map(|s| {
+			let last = s.long.last().map(|v| format!("{:.1}%", v * 100.0)).unwrap_or_default();
+			span().child((span().style(format!("color: {}", s.color)).child("■ "), format!("{} {last}", s.label)))
+		})
+		.collect::<Vec<_>>();
+
+	div().child((
+		leptos::svg::svg()
+			.attr("viewBox", format!("0 0 {W} {H}"))
+			.attr("preserveAspectRatio", "none")
+			.class("w-full h-20")
+			.child((even, lines)),
+		div()
+			.class("flex flex-wrap gap-x-3 text-xs")
+			.child((legend, span().class("text-gray-500").child(format!("% long, {:.1}–{:.1}", lo * 100.0, hi * 100.0)))),
+	))
+}
+
+#[component]
+fn LsrSearch(rows: Memo<Vec<LsrRow>>, selected_pairs: RwSignal<Vec<Pair>>) -> impl IntoView {
 	let search_input = RwSignal::new(String::default());
 
-	///HACK: for now, using simple substring matching
-	fn fzf(s: &str, available: &[RenderedLsr]) -> Vec<RenderedLsr> {
-		available.iter().filter(|v| v.rend.to_lowercase().contains(&s.to_lowercase())).cloned().collect()
+	/// Ranked by [`fuzzy::score`](crate::fuzzy::score) over base symbols, best first, each with the matched char positions in its base.
+	fn fzf(s: &str, available: &[LsrRow]) -> Vec<(LsrRow, Vec<usize>)> {
+		let (query, _) = crate::fuzzy::symbol_key(s);
+		let mut hits: Vec<_> = available
+			.iter()
+			.filter_map(|v| {
+				let (key, dropped) = crate::fuzzy::symbol_key(&v.pair.base().to_string());
+				let m = crate::fuzzy::score(&query, &key)?;
+				Some((m.score, key.len(), v.clone(), m.indices.into_iter().map(|i| i + dropped).collect()))
+			})
+			.collect();
+		// on a tie the shorter symbol is the likelier target
+		hits.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+		hits.into_iter().map(|(_, _, v, indices)| (v, indices)).collect()
 	}
 	let filtered_items = Memo::new(move |_| {
 		let search = search_input.read();
-		if search.is_empty() { vec![] } else { fzf(&search, &rendered_lsrs.read()) }
+		if search.is_empty() { vec![] } else { fzf(&search, &rows.read()) }
 	});
 
 	let handle_search_input = move |ev: web_sys::Event| {
@@ -241,7 +394,7 @@ fn LsrSearch(rendered_lsrs: Memo<Vec<RenderedLsr>>, selected_pairs: RwSignal<Vec
 		*search_input.write() = new_input;
 	};
 
-	let handle_select_click = StoredValue::new(move |item: RenderedLsr| {
+	let handle_select_click = StoredValue::new(move |item: LsrRow| {
 		// Add to selected pairs
 		selected_pairs.update(|pairs| {
 			if !pairs.contains(&item.pair) {
@@ -249,15 +402,8 @@ fn LsrSearch(rendered_lsrs: Memo<Vec<RenderedLsr>>, selected_pairs: RwSignal<Vec
 			}
 		});
 
-		// Update URL to reflect selection (omit -USDT suffix for cleaner URLs)
-		if let Some(window) = web_sys::window() {
-			let pairs = selected_pairs.get();
-			let lsr_param = pairs.iter().map(|p| p.base().to_string()).collect::<Vec<_>>().join(",");
-			if let Ok(history) = window.history() {
-				let new_url = format!("/dashboards?lsr={lsr_param}");
-				let _ = history.push_state_with_url(&JsValue::NULL, "", Some(&new_url));
-			}
-		}
+		// Update URL to reflect selection
+		push_selection_url(&selected_pairs.get());
 
 		*search_input.write() = String::default();
 	});
@@ -292,7 +438,7 @@ fn LsrSearch(rendered_lsrs: Memo<Vec<RenderedLsr>>, selected_pairs: RwSignal<Vec
 					ev.prevent_default();
 					let idx = focused_index.get();
 					if idx >= 0 && (idx as usize) < items_count {
-						let selected_item = filtered_items.get()[idx as usize].clone();
+						let selected_item = filtered_items.get()[idx as usize].0.clone();
 						handle_select_click.with_value(|f| f(selected_item));
 					}
 					focused_index.set(0);
@@ -319,8 +465,8 @@ fn LsrSearch(rendered_lsrs: Memo<Vec<RenderedLsr>>, selected_pairs: RwSignal<Vec
 			.style(move || if filtered_items.get().is_empty() { "display: none;" } else { "display: block;" })
 			.child(ForEnumerate(ForEnumerateProps {
 				each: move || filtered_items.get(),
-				key: |item| item.pair,
-				children: move |i: ReadSignal<usize>, item: RenderedLsr| {
+				key: |(item, _)| item.pair,
+				children: move |i: ReadSignal<usize>, (item, hits): (LsrRow, Vec<usize>)| {
 					let is_focused = move || focused_index.get() == *i.read() as i32;
 					static HOVER_BG: &str = "bg-gray-100";
 
@@ -335,32 +481,115 @@ fn LsrSearch(rendered_lsrs: Memo<Vec<RenderedLsr>>, selected_pairs: RwSignal<Vec
 							let item_clone = item.clone();
 							move |_| handle_select_click.with_value(|f| f(item_clone.clone())) //wtf, why must I clone twice?
 						})
-						.child(item.rend.clone())
+						.child(highlighted(&item, &hits))
 				},
 			})),
 	))
 }
 
+/// `item`'s base symbol with the chars that matched the search emphasized, then its summary.
+fn highlighted(item: &LsrRow, hits: &[usize]) -> impl IntoView {
+	let base = crate::fuzzy::highlight(&item.pair.base().to_string(), hits)
+		.into_iter()
+		.map(|(chunk, hit)| span().class(if hit { "font-bold text-blue-600" } else { "" }).child(chunk))
+		.collect::<Vec<_>>();
+	(base, span().class("ml-2 text-gray-500").child(item.summary()))
+}
+
+/// The Binance list, with Bybit alongside for the divergence table.
 #[server]
-async fn build_lsrs() -> Result<RenderedLsrs, ServerFnError> {
-	let lsrs = super::_core::load::<data::SortedLsrs>()
+pub async fn lsr_snapshot() -> Result<LsrSnapshot, ServerFnError> {
+	use data::LsrVenue;
+
+	let binance = super::_core::load_with::<data::SortedLsrs>(&LsrVenue::Binance)
 		.await
 		.map_err(|e| {
 			tracing::error!("Failed to load LSR data: {e:?}");
 			ServerFnError::new(format!("Failed to load LSR data: {e}"))
 		})?
 		.data;
-	Ok(lsrs.into())
+	// the second venue only feeds the divergence table; Binance alone still makes the panel
+	let divergence = match super::_core::load_with::<data::SortedLsrs>(&LsrVenue::Bybit).await {
+		Ok(bybit) => {
+			let (rows, shared) = data::divergence(&binance, &bybit.data, 20);
+			if shared == 0 {
+				Err("No pairs with LSR data on both Binance and Bybit".to_owned())
+			} else {
+				Ok(Divergence {
+					venues: (LsrVenue::Binance.to_string(), LsrVenue::Bybit.to_string()),
+					rows: rows.into_iter().map(|(pair, a, b)| (pair, a * 100.0, b * 100.0)).collect(),
+					shared,
+				})
+			}
+		}
+		Err(e) => {
+			tracing::warn!("Failed to load Bybit LSR data: {e:?}");
+			Err(format!("No Bybit LSR data ({e})"))
+		}
+	};
+	Ok(LsrSnapshot {
+		rows: binance
+			.iter()
+			.filter_map(|lsr| {
+				let (first, last) = (lsr.first()?.long(), lsr.last()?.long());
+				Some(LsrRow {
+					pair: lsr.pair,
+					long: last * 100.0,
+					change: (last - first) * 100.0,
+				})
+			})
+			.collect(),
+		venue: LsrVenue::Binance.market(),
+		tf: data::TF.to_owned(),
+		span: data::SPAN.to_owned(),
+		collected: binance.len(),
+		listed: binance.__total_pairs_on_exchange.unwrap_or(binance.len()),
+		divergence,
+	})
+}
+#[server]
+async fn lsr_history(pair: Pair) -> Result<LsrHistory, ServerFnError> {
+	let listed = super::_core::load_with::<data::SortedLsrs>(&data::LsrVenue::Binance)
+		.await
+		.map_err(|e| ServerFnError::new(format!("Failed to load LSR data: {e}")))?
+		.data;
+	if !listed.iter().any(|lsr| lsr.pair == pair) {
+		return Err(ServerFnError::new(format!("{pair} is not among the collected pairs")));
+	}
+	// the Binance poll keeps every listed pair's month topped up; one it hasn't reached yet is fetched on its own
+	let month = super::_core::load_with::<data::RatioMonth>(&data::MonthParams { pair: pair.to_string() })
+		.await
+		.map_err(|e| ServerFnError::new(format!("Failed to load {pair} ratios: {e}")))?
+		.data;
+	let series = month
+		.series
+		.iter()
+		.map(|(ratio, points)| RatioSeries {
+			label: ratio.label().into(),
+			color: ratio_color(*ratio).into(),
+			time: points.iter().map(|p| p.0).collect(),
+			long: points.iter().map(|p| p.1).collect(),
+		})
+		.collect();
+	Ok(LsrHistory { pair, series })
+}
+#[cfg(feature = "ssr")]
+pub(super) fn register(scheduler: &mut super::_core::Scheduler) {
+	for venue in data::LsrVenue::ALL {
+		scheduler.register_with::<data::SortedLsrs>(venue);
+	}
 }
 #[cfg(feature = "ssr")]
 impl super::_core::SourceData for data::SortedLsrs {
+	type Params = data::LsrVenue;
+
 	fn decay_horizon() -> v_utils::trades::Timeframe {
 		"5m".into()
 	}
 
-	async fn fetch() -> color_eyre::eyre::Result<Self> {
-		let range = (24 * 12 + 1).into(); // 24h, given `5m` tf
-		data::get("5m".into(), range).await
+	async fn fetch(venue: &data::LsrVenue) -> color_eyre::eyre::Result<Self> {
+		let range = (24 * 12 + 1).into(); // `SPAN`, given `TF`
+		data::get(*venue, data::TF.into(), range).await
 	}
 
 	fn fmt_progress(loaded: usize, target: usize) -> String {
@@ -368,11 +597,29 @@ impl super::_core::SourceData for data::SortedLsrs {
 	}
 }
 #[cfg(feature = "ssr")]
-impl From<data::SortedLsrs> for RenderedLsrs {
-	fn from(s: data::SortedLsrs) -> Self {
-		Self {
-			v: s.iter().map(|lsr| RenderedLsr::new(lsr.pair, lsr.display_short().unwrap(), lsr.last().unwrap().long())).collect(),
-			outliers: s.display_outliers(10),
-		}
+impl super::_core::SourceData for data::RatioMonth {
+	type Params = data::MonthParams;
+
+	/// The points' own period: the [`data::SortedLsrs`] poll rewrites each month as a new point comes out.
+	fn decay_horizon() -> v_utils::trades::Timeframe {
+		data::MONTH_PERIOD.into()
+	}
+
+	/// Already a month of points.
+	fn history_retention() -> Option<jiff::SignedDuration> {
+		None
+	}
+
+	async fn fetch(params: &data::MonthParams) -> color_eyre::eyre::Result<Self> {
+		let pair: Pair = params.pair.parse().map_err(|e| color_eyre::eyre::eyre!("unparseable pair {}: {e:?}", params.pair))?;
+		data::month(pair).await
+	}
+}
+#[cfg(feature = "ssr")]
+fn ratio_color(ratio: data::Ratio) -> &'static str {
+	match ratio {
+		data::Ratio::Global => "#fbbf24",
+		data::Ratio::TopAccounts => "#60a5fa",
+		data::Ratio::TopPositions => "#f472b6",
 	}
 }
@@ -0,0 +1,144 @@
+//! Tables over the typed [`LsrSnapshot`]: every pair, sortable by column and shaded by positioning, and
+//! the cross-venue divergence.
+use leptos::{ev, html::*, prelude::*};
+
+use super::{Divergence, LsrRow, LsrSnapshot};
+
+/// Below this share of listed pairs with data, the coverage note is flagged.
+const COVERAGE_WARN: f64 = 0.7;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Column {
+	Pair,
+	Long,
+	Change,
+}
+
+#[component]
+pub fn LsrTable(snapshot: LsrSnapshot) -> impl IntoView {
+	// starts as the payload comes: most shorted first
+	let sort = RwSignal::new((Column::Long, false));
+	let rows = StoredValue::new(snapshot.rows.clone());
+
+	let sorted = move || {
+		let (col, desc) = sort.get();
+		let mut rows = rows.get_value();
+		rows.sort_by(|a, b| {
+			let ord = match col {
+				Column::Pair => a.pair.to_string().cmp(&b.pair.to_string()),
+				Column::Long => a.long.total_cmp(&b.long),
+				Column::Change => a.change.total_cmp(&b.change),
+			};
+			if desc { ord.reverse() } else { ord }
+		});
+		rows
+	};
+
+	let header = move |col: Column, label: String| {
+		th().class("px-2 py-1 text-left cursor-pointer select-none")
+			.on(ev::click, move |_| {
+				sort.update(|(c, desc)| {
+					if *c == col {
+						*desc = !*desc;
+					} else {
+						*c = col;
+						// numbers read best biggest-first; names alphabetically
+						*desc = col != Column::Pair;
+					}
+				})
+			})
+			.child(move || {
+				let (c, desc) = sort.get();
+				let arrow = if c != col {
+					""
+				} else if desc {
+					" ▼"
+				} else {
+					" ▲"
+				};
+				format!("{label}{arrow}")
+			})
+	};
+
+	let coverage = snapshot.collected as f64 / snapshot.listed.max(1) as f64;
+	let average = snapshot.rows.iter().map(|r| r.long).sum::<f64>() / snapshot.rows.len().max(1) as f64;
+
+	div().child((
+		div().class("max-h-72 overflow-y-auto").child(table().class("w-full text-sm font-mono").child((
+			thead().class("sticky top-0 bg-white").child(tr().child((
+				header(Column::Pair, "Pair".to_owned()),
+				header(Column::Long, "% long".to_owned()),
+				header(Column::Change, format!("Δ {}", snapshot.span)),
+			))),
+			tbody().child(move || sorted().into_iter().map(row).collect::<Vec<_>>()),
+		))),
+		div().class("mt-1 text-xs text-gray-500").child((
+			span()
+				.style(if coverage < COVERAGE_WARN { "color: #f59e0b" } else { "" })
+				.child(format!("{}/{}", snapshot.collected, snapshot.listed)),
+			format!(" pairs on {} · {} bars · average {average:.2}% long", snapshot.venue, snapshot.tf),
+		)),
+	))
+}
+
+fn row(r: LsrRow) -> impl IntoView {
+	tr().child((
+		td().class("px-2").child(r.pair.base().to_string()),
+		td().class("px-2 text-right").style(format!("background: {}", long_shade(r.long))).child(format!("{:.2}", r.long)),
+		td().class("px-2 text-right")
+			.style(format!("color: {}", change_color(r.change)))
+			.child(format!("{:+.2}", r.change)),
+	))
+}
+
+/// Red for crowded shorts, green for crowded longs, deeper the further from even; saturates 25pp out.
+fn long_shade(long: f64) -> String {
+	let d = ((long - 50.0) / 25.0).clamp(-1.0, 1.0);
+	let (r, g, b) = if d >= 0.0 { (34, 197, 94) } else { (239, 68, 68) };
+	format!("rgba({r},{g},{b},{:.2})", 0.45 * d.abs())
+}
+
+fn change_color(change: f64) -> &'static str {
+	if change > 0.0 {
+		"#16a34a"
+	} else if change < 0.0 {
+		"#dc2626"
+	} else {
+		"inherit"
+	}
+}
+
+#[component]
+pub fn DivergenceTable(divergence: Result<Divergence, String>) -> impl IntoView {
+	let d = match divergence {
+		Ok(d) => d,
+		Err(why) => return div().class("mt-4 text-xs text-gray-500").child(format!("Divergence unavailable: {why}")).into_any(),
+	};
+	div()
+		.class("mt-4")
+		.child((
+			table().class("w-full text-sm font-mono").child((
+				thead().child(tr().child((
+					th().class("px-2 text-left").child("Divergence"),
+					th().class("px-2 text-right").child(d.venues.0.clone()),
+					th().class("px-2 text-right").child(d.venues.1.clone()),
+					th().class("px-2 text-right").child("Δ"),
+				))),
+				tbody().child(
+					d.rows
+						.iter()
+						.map(|(pair, a, b)| {
+							tr().child((
+								td().class("px-2").child(pair.base().to_string()),
+								td().class("px-2 text-right").style(format!("background: {}", long_shade(*a))).child(format!("{a:.2}")),
+								td().class("px-2 text-right").style(format!("background: {}", long_shade(*b))).child(format!("{b:.2}")),
+								td().class("px-2 text-right").child(format!("{:+.2}", a - b)),
+							))
+						})
+						.collect::<Vec<_>>(),
+				),
+			)),
+			div().class("mt-1 text-xs text-gray-500").child(format!("% long; {} pairs listed on both", d.shared)),
+		))
+		.into_any()
+}
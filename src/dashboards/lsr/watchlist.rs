@@ -0,0 +1,185 @@
+//! Named LSR pair selections, saved per user and shared by a short `?wl=<code>` link. Anonymous visitors
+//! don't get the bar; they keep the selection in `?lsr=` only.
+use leptos::{ev, html::*, prelude::*};
+use serde::{Deserialize, Serialize};
+use v_utils::trades::Pair;
+
+/// Pairs per list; about what the panel can show at once without scrolling being the whole experience.
+#[cfg_attr(not(feature = "ssr"), allow(dead_code))]
+const MAX_PAIRS: usize = 100;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Watchlist {
+	pub id: String,
+	pub name: String,
+	/// Base symbols, as in `?lsr=`.
+	pub bases: Vec<String>,
+	pub share_code: String,
+}
+impl Watchlist {
+	pub fn pairs(&self) -> Vec<Pair> {
+		super::parse_pairs(self.bases.iter().map(String::as_str))
+	}
+
+	fn share_url(&self) -> String {
+		format!("/dashboards?wl={}", self.share_code)
+	}
+}
+
+#[component]
+pub fn WatchlistBar(selected_pairs: RwSignal<Vec<Pair>>) -> impl IntoView {
+	let trigger = RwSignal::new(());
+	let saved = Resource::new(move || trigger.get(), |_| list_watchlists());
+	let current = RwSignal::new(None::<Watchlist>);
+	let name = RwSignal::new(String::new());
+	let status = RwSignal::new(None::<String>);
+
+	let pick = move |id: String| {
+		let picked = saved.get_untracked().and_then(|r| r.ok()).flatten().and_then(|ls| ls.into_iter().find(|w| w.id == id));
+		if let Some(w) = &picked {
+			let pairs = w.pairs();
+			super::push_selection_url(&pairs);
+			selected_pairs.set(pairs);
+			name.set(w.name.clone());
+		}
+		current.set(picked);
+		status.set(None);
+	};
+
+	let save = move |_| {
+		let bases: Vec<String> = selected_pairs.get_untracked().iter().map(|p| p.base().to_string()).collect();
+		let list_name = name.get_untracked();
+		leptos::task::spawn_local(async move {
+			match save_watchlist(list_name, bases).await {
+				Ok(w) => {
+					status.set(Some(format!("Saved {}", w.name)));
+					current.set(Some(w));
+					trigger.update(|_| ());
+				}
+				Err(e) => status.set(Some(e.to_string())),
+			}
+		});
+	};
+
+	let delete = move |_| {
+		let Some(w) = current.get_untracked() else { return };
+		leptos::task::spawn_local(async move {
+			match delete_watchlist(w.id).await {
+				Ok(()) => {
+					status.set(Some(format!("Deleted {}", w.name)));
+					current.set(None);
+					trigger.update(|_| ());
+				}
+				Err(e) => status.set(Some(e.to_string())),
+			}
+		});
+	};
+
+	move || {
+		// `None` for anonymous visitors — they stay on plain `?lsr=` links
+		let Some(Ok(Some(lists))) = saved.get() else { return None };
+		let current_id = current.get().map(|w| w.id);
+		Some(
+			div().class("flex flex-wrap items-center gap-2 mt-2 text-sm").child((
+				select().class("p-1 border rounded").on(ev::change, move |ev| pick(event_target_value(&ev))).child((
+					option().value("").selected(current_id.is_none()).child("Watchlists…"),
+					lists
+						.into_iter()
+						.map(|w| option().value(w.id.clone()).selected(current_id.as_ref() == Some(&w.id)).child(w.name))
+						.collect::<Vec<_>>(),
+				)),
+				input()
+					.class("p-1 border rounded")
+					.attr("type", "text")
+					.attr("placeholder", "List name")
+					.prop("value", name)
+					.on(ev::input, move |ev| name.set(event_target_value(&ev))),
+				button().class("px-2 py-1 border rounded").attr("type", "button").on(ev::click, save).child("Save"),
+				move || {
+					current.get().map(|w| {
+						(
+							a().class("underline").href(w.share_url()).child("Share link"),
+							button().class("px-2 py-1 border rounded").attr("type", "button").on(ev::click, delete).child("Delete"),
+						)
+					})
+				},
+				move || status.get().map(|s| span().class("text-gray-500").child(s)),
+			)),
+		)
+	}
+}
+
+/// `None` when nobody is logged in.
+#[server]
+async fn list_watchlists() -> Result<Option<Vec<Watchlist>>, ServerFnError> {
+	let Some(user) = crate::app::server_impl::get_current_user_impl().await? else {
+		return Ok(None);
+	};
+	let rows = db()?.list_lsr_watchlists(&user.id).await.map_err(|e| ServerFnError::new(format!("DB error: {e}")))?;
+	Ok(Some(rows.into_iter().map(Watchlist::from).collect()))
+}
+
+/// Saving under an existing name replaces that list's pairs; its share link stays the same.
+#[server]
+async fn save_watchlist(name: String, bases: Vec<String>) -> Result<Watchlist, ServerFnError> {
+	let user = crate::app::server_impl::get_current_user_impl().await?.ok_or_else(|| ServerFnError::new("Not logged in"))?;
+
+	let name = name.trim().to_string();
+	if name.is_empty() {
+		return Err(ServerFnError::new("Name the list first"));
+	}
+	if name.chars().count() > 64 {
+		return Err(ServerFnError::new("List name too long (max 64 characters)"));
+	}
+	if bases.is_empty() {
+		return Err(ServerFnError::new("Select some pairs first"));
+	}
+	if bases.len() > MAX_PAIRS {
+		return Err(ServerFnError::new(format!("Too many pairs (max {MAX_PAIRS})")));
+	}
+	if let Some(bad) = bases.iter().find(|b| b.is_empty() || b.len() > 20 || !b.chars().all(|c| c.is_ascii_alphanumeric())) {
+		return Err(ServerFnError::new(format!("Not a symbol: {bad}")));
+	}
+
+	let id = uuid::Uuid::new_v4().to_string();
+	let share_code = uuid::Uuid::new_v4().simple().to_string()[..10].to_owned();
+	let row = db()?
+		.save_lsr_watchlist(&id, &user.id, &name, &bases.join(","), &share_code)
+		.await
+		.map_err(|e| ServerFnError::new(format!("DB error: {e}")))?;
+	Ok(row.into())
+}
+
+#[server]
+async fn delete_watchlist(id: String) -> Result<(), ServerFnError> {
+	let user = crate::app::server_impl::get_current_user_impl().await?.ok_or_else(|| ServerFnError::new("Not logged in"))?;
+	db()?.delete_lsr_watchlist(&user.id, &id).await.map_err(|e| ServerFnError::new(format!("DB error: {e}")))?;
+	Ok(())
+}
+
+/// Anyone with the link can load the list, logged in or not.
+#[server]
+pub async fn shared_watchlist(share_code: String) -> Result<Option<Watchlist>, ServerFnError> {
+	let row = db()?
+		.get_lsr_watchlist_by_share_code(&share_code)
+		.await
+		.map_err(|e| ServerFnError::new(format!("DB error: {e}")))?;
+	Ok(row.map(Watchlist::from))
+}
+
+#[cfg(feature = "ssr")]
+fn db() -> Result<crate::auth::Database, ServerFnError> {
+	use_context::<crate::auth::Database>().ok_or_else(|| ServerFnError::new("Database not available"))
+}
+
+#[cfg(feature = "ssr")]
+impl From<crate::auth::LsrWatchlistRow> for Watchlist {
+	fn from(r: crate::auth::LsrWatchlistRow) -> Self {
+		Self {
+			id: r.id,
+			name: r.name,
+			bases: r.pairs.split(',').filter(|s| !s.is_empty()).map(str::to_owned).collect(),
+			share_code: r.share_code,
+		}
+	}
+}
@@ -0,0 +1,71 @@
+//! Market breadth on BTC's bar grid, for the sub-pane under the chart: how many pairs carry a move, as
+//! opposed to how far the average went. A broad rally lifts most pairs above their start and keeps the
+//! advance/decline line climbing; a narrow one moves the average on a handful of names.
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use v_utils::trades::Pair;
+
+use super::{
+	data::Line,
+	overlays::{align, bar_returns, line, window},
+};
+
+#[derive(Deserialize, Serialize)]
+pub struct Breadth {
+	above_start: Line, // % of listed pairs above their own first close
+	ad_line: Line,     // running sum of advancing minus declining pairs, per bar
+	new_highs: Line,   // pairs closing above their trailing-`window` high
+	new_lows: Line,    // pairs closing below their trailing-`window` low
+	window: usize,
+}
+
+/// `closes` are the normalized log-returns the chart carries (0 at each pair's first bar).
+pub fn build(closes: &HashMap<Pair, Vec<(i64, f64)>>, grid: &[i64]) -> Option<Breadth> {
+	let n = grid.len();
+	if n < 2 {
+		return None;
+	}
+	let pairs: Vec<Pair> = closes.keys().copied().collect();
+	let aligned = align(&pairs, closes, grid);
+	let rets = bar_returns(&aligned);
+	let window = window(n);
+
+	let mut above_start = Vec::with_capacity(n);
+	let mut ad_line = Vec::with_capacity(n);
+	let mut new_highs = Vec::with_capacity(n);
+	let mut new_lows = Vec::with_capacity(n);
+	let mut ad = 0.0;
+	for i in 0..n {
+		let listed: Vec<f64> = aligned.iter().filter_map(|row| row[i]).collect();
+		let above = listed.iter().filter(|v| **v > 0.0).count();
+		above_start.push(if listed.is_empty() { 0.0 } else { 100.0 * above as f64 / listed.len() as f64 });
+
+		let (adv, dec) = rets.iter().filter_map(|r| r[i]).fold((0, 0), |(a, d), r| (a + (r > 0.0) as i64, d + (r < 0.0) as i64));
+		ad += (adv - dec) as f64;
+		ad_line.push(ad);
+
+		let (mut highs, mut lows) = (0, 0);
+		if i >= window {
+			for row in &aligned {
+				let Some(v) = row[i] else { continue };
+				let prior: Vec<f64> = row[i - window..i].iter().flatten().copied().collect();
+				if prior.is_empty() {
+					continue;
+				}
+				highs += prior.iter().all(|p| v > *p) as u32;
+				lows += prior.iter().all(|p| v < *p) as u32;
+			}
+		}
+		new_highs.push(highs as f64);
+		new_lows.push(lows as f64);
+	}
+
+	Some(Breadth {
+		above_start: line(grid, &above_start),
+		ad_line: line(grid, &ad_line),
+		new_highs: line(grid, &new_highs),
+		new_lows: line(grid, &new_lows),
+		window,
+	})
+}
@@ -1,6 +1,10 @@
-use std::{collections::HashMap, sync::LazyLock, time::Duration};
+use std::{
+	collections::{HashMap, HashSet},
+	sync::LazyLock,
+	time::Duration,
+};
 
-use color_eyre::eyre::{Result, bail};
+use color_eyre::eyre::{Result, WrapErr as _, bail, eyre};
 use futures::{
 	lock::Mutex,
 	stream::{self, StreamExt as _},
@@ -11,6 +15,13 @@ use tracing::instrument;
 use v_exchanges::prelude::*;
 use v_utils::trades::{Pair, Timeframe};
 
+use super::{
+	ChartParams, Venue,
+	breadth::{self, Breadth},
+	overlays::{self, BTCDOM, Overlay},
+};
+use crate::dashboards::_core::{Progress, cassette};
+
 /// category10, cycled across highlighted (non-BTC) series
 const PALETTE: [&str; 10] = ["#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f", "#bcbd22", "#17becf"];
 
@@ -23,25 +34,122 @@ pub struct MarketStructureChart {
 	values: Vec<Line>,        // per-series, parallel to `series`, in draw order
 	series: Vec<SeriesMeta>,  // interactive highlights only: non-BTC highlights, then BTC (painted on top)
 	legend: Vec<LegendEntry>, // legend order: top…, BTC (middle), …bottom
+	#[serde(default)]
+	overlays: Vec<Overlay>, // whole-market series, each togglable from the legend
+	#[serde(default)]
+	breadth: Option<Breadth>, // sub-pane under the chart
 	title: String,
 }
-#[instrument(skip(exchange))]
-pub async fn market_structure_json(limit: RequestRange, tf: Timeframe, exchange: &mut dyn Exchange, instrument: Instrument) -> Result<MarketStructureChart> {
-	tracing::info!("Building market structure for {} {instrument} with limit={limit:?}, tf={tf:?}", exchange.name());
+#[instrument]
+pub async fn market_structure_json(params: ChartParams) -> Result<MarketStructureChart> {
+	let ChartParams { venue, .. } = params;
+	let tf: Timeframe = params.tf.slug().into();
+	let bars = params.bars();
+	tracing::info!("Building market structure for {venue} with bars={bars}, tf={tf:?}");
+
+	// list every listing first, so the merged view knows its full universe (and progress target) up front.
+	// A pair listed on several venues is kept only under the first one to list it.
+	let mut seen = HashSet::new();
+	let mut plan = Vec::new();
+	for (client, instrument) in listings(venue) {
+		let mut exchange = client.lock().await;
+		let pairs = match usdt_pairs(&mut **exchange, instrument).await {
+			Ok(pairs) => pairs,
+			// one venue down shouldn't blank the merged view; a single-venue view has nothing else to show
+			Err(e) if venue == Venue::Merged => {
+				tracing::warn!("skipping {} {instrument} in merged view: {e:?}", exchange.name());
+				continue;
+			}
+			Err(e) => return Err(e),
+		};
+		let fresh: Vec<Pair> = pairs.into_iter().filter(|p| seen.insert(*p)).collect();
+		tracing::info!("{} unique USDT pairs from {} {instrument}", fresh.len(), exchange.name());
+		plan.push((client, instrument, fresh));
+	}
+	// BTCDOM overlays every venue, but only Binance lists it, as a perp; a venue that doesn't list it gets it from there
+	let btcdom: Pair = BTCDOM.try_into().unwrap();
+	if seen.insert(btcdom) {
+		plan.push((&BINANCE, Instrument::Perp, vec![btcdom]));
+	}
+
+	let progress = Progress::of::<MarketStructureChart>(&params);
+	progress.set_target(seen.len());
+	let (mut closes, mut volumes) = (Series::new(), Series::new());
+	for (client, instrument, pairs) in plan {
+		let exchange = client.lock().await;
+		let (c, v) = collect_data(&pairs, tf, bars, instrument, &**exchange, progress).await;
+		closes.extend(c);
+		volumes.extend(v);
+	}
 
-	let exch_info = match exchange.exchange_info(instrument).await {
-		Ok(info) => info,
+	let btc: Pair = "BTC-USDT".try_into().unwrap();
+	if !closes.contains_key(&btc) {
+		tracing::error!("BTC-USDT missing from results. attempted: {}, succeeded: {}", seen.len(), closes.len());
+		bail!("Failed to fetch data for BTC-USDT, aborting. Check logs for individual fetch errors.");
+	}
+	let caps: HashMap<Pair, f64> = match market_caps().await {
+		Ok(caps) => closes.keys().filter_map(|p| Some((*p, *caps.get(&crate::fuzzy::symbol_key(&p.to_string()).0)?))).collect(),
+		// only the cap-weighted index needs them
 		Err(e) => {
-			tracing::error!("Failed to fetch exchange info: {e:?}");
-			return Err(e.into());
+			tracing::warn!("no market caps, leaving out the cap-weighted index: {e:?}");
+			HashMap::new()
 		}
 	};
+	Ok(build_chart(closes, &volumes, &caps, seen.len(), tf, venue))
+}
+/// USD market cap by ticker, as [`crate::fuzzy::symbol_key`] spells it, of CoinGecko's top 250 by cap. A
+/// ticker several coins share keeps the largest; pairs outside the top 250 are too small to move the index.
+async fn market_caps() -> Result<HashMap<String, f64>> {
+	let body = cassette::http("coingecko/markets/250", async {
+		Ok(reqwest::Client::new()
+			.get("https://api.coingecko.com/api/v3/coins/markets")
+			.query(&[("vs_currency", "usd"), ("order", "market_cap_desc"), ("per_page", "250"), ("page", "1")])
+			.send()
+			.await?
+			.error_for_status()?
+			.text()
+			.await?)
+	})
+	.await?;
 
-	let all_pairs = exch_info.usdt_pairs().collect::<Vec<Pair>>();
-	tracing::info!("Found {} USDT pairs from exchange info", all_pairs.len());
-
-	let closes = collect_data(&all_pairs, tf, limit, instrument, &*exchange).await?;
-
+	#[derive(Deserialize)]
+	struct Coin {
+		symbol: String,
+		market_cap: Option<f64>,
+	}
+	let coins: Vec<Coin> = serde_json::from_str(&body).wrap_err("malformed CoinGecko markets response")?;
+	let mut caps = HashMap::new();
+	for Coin { symbol, market_cap } in coins {
+		if let Some(cap) = market_cap.filter(|c| *c > 0.0) {
+			// served largest first
+			caps.entry(symbol.to_uppercase()).or_insert(cap);
+		}
+	}
+	Ok(caps)
+}
+async fn usdt_pairs(exchange: &mut dyn Exchange, instrument: Instrument) -> Result<Vec<Pair>> {
+	cassette::tape(&format!("{}/{instrument}/usdt_pairs", exchange.name()), async {
+		match exchange.exchange_info(instrument).await {
+			Ok(info) => Ok(info.usdt_pairs().collect()),
+			Err(e) => {
+				tracing::error!("Failed to fetch exchange info: {e:?}");
+				Err(e.into())
+			}
+		}
+	})
+	.await
+}
+/// Lays out normalized log-return series (each starting at 0) as the chart payload: the ln(n) best
+/// and worst performers become highlighted series, BTC is pinned gold, the rest go to the grey bulk.
+/// BTCDOM, when it fetched, is pulled out of the pairs and only drawn as an overlay. `volumes` (quote, per
+/// bar) and `caps` (current USD market cap, for the pairs it's known for) only feed the weighted indices.
+fn build_chart(mut closes: Series, volumes: &Series, caps: &HashMap<Pair, f64>, mut total_pairs: usize, tf: Timeframe, venue: Venue) -> MarketStructureChart {
+	let btcdom_pair: Pair = BTCDOM.try_into().unwrap();
+	let btcdom = closes.remove(&btcdom_pair);
+	if btcdom.is_some() {
+		total_pairs -= 1;
+	}
+	let closes = &closes;
 	let mut performance: Vec<(Pair, f64)> = closes.iter().map(|(k, v)| (*k, v.last().unwrap().1 - v.first().unwrap().1)).collect();
 	performance.sort_by(|a, b| a.1.total_cmp(&b.1));
 	let n_samples = (performance.len() as f64).ln().round() as usize;
@@ -55,6 +163,9 @@ pub async fn market_structure_json(limit: RequestRange, tf: Timeframe, exchange:
 
 	let btc: Pair = "BTC-USDT".try_into().unwrap();
 	let contains_btc = closes.contains_key(&btc);
+	let grid: Vec<i64> = closes.get(&btc).map(|v| v.iter().map(|p| p.0).collect()).unwrap_or_default();
+	let overlays = overlays::build(closes, volumes, caps, btcdom.as_deref(), &grid);
+	let breadth = breadth::build(closes, &grid);
 
 	let label_for = |pair: Pair, symbol: Option<&str>| -> String {
 		let p = performance.iter().find(|a| a.0 == pair).unwrap().1;
@@ -85,6 +196,7 @@ pub async fn market_structure_json(limit: RequestRange, tf: Timeframe, exchange:
 		legend.push(LegendEntry {
 			label: label_for(pair, None),
 			color,
+			pair: Some(pair.to_string()),
 		});
 	};
 	for &pair in &top {
@@ -95,6 +207,7 @@ pub async fn market_structure_json(limit: RequestRange, tf: Timeframe, exchange:
 		legend.push(LegendEntry {
 			label: label_for(btc, Some("~BTC~")),
 			color: "gold".to_string(),
+			pair: Some(btc.to_string()),
 		});
 	}
 	for &pair in bottom.iter().rev() {
@@ -103,7 +216,7 @@ pub async fn market_structure_json(limit: RequestRange, tf: Timeframe, exchange:
 
 	// greys never become interactive series — collected as `bulk` for the canvas primitive
 	let mut bulk = Vec::new();
-	for (pair, v) in &closes {
+	for (pair, v) in closes {
 		if *pair == btc || color_of.contains_key(pair) {
 			continue;
 		}
@@ -141,40 +254,59 @@ pub async fn market_structure_json(limit: RequestRange, tf: Timeframe, exchange:
 		hi = hi.max(v.last().unwrap().0);
 	}
 	let hours = (((hi - lo) + tf.duration().as_secs() as i64 * 2/*compensate for off-by-ones*/) as f64 / 3600.0).round() as i64;
-	let title = format!("Last {hours}h of {}/{} pairs on {instrument}", closes.len(), all_pairs.len());
+	let span = match hours {
+		..72 => format!("{hours}h"),
+		_ => format!("{}d", (hours as f64 / 24.0).round()),
+	};
+	let title = format!("Last {span} of {}/{total_pairs} pairs on {venue}", closes.len());
 
-	Ok(MarketStructureChart {
+	MarketStructureChart {
 		bulk,
 		values,
 		series,
 		legend,
+		overlays,
+		breadth,
 		title,
-	})
+	}
 }
-/// One long-lived client, reused across every poll. The post-ban cooldown gate lives on the client
-/// (`banned_until`), so rebuilding it each poll would forget the ban and keep re-hitting Binance —
-/// which renews and escalates it. Reuse is what makes the gate actually stop the bleeding.
-static BINANCE: LazyLock<Mutex<Box<dyn Exchange>>> = LazyLock::new(|| {
-	let mut ex = ExchangeName::Binance.init_client();
+/// One long-lived client per exchange, reused across every poll and every venue on it. The post-ban
+/// cooldown gate lives on the client (`banned_until`), so rebuilding it each poll would forget the ban
+/// and keep re-hitting the exchange — which renews and escalates it. Reuse is what makes the gate
+/// actually stop the bleeding.
+static BINANCE: LazyLock<Mutex<Box<dyn Exchange>>> = LazyLock::new(|| client(ExchangeName::Binance));
+static BYBIT: LazyLock<Mutex<Box<dyn Exchange>>> = LazyLock::new(|| client(ExchangeName::Bybit));
+fn client(name: ExchangeName) -> Mutex<Box<dyn Exchange>> {
+	let mut ex = name.init_client();
 	ex.set_retry_config(RetryConfig {
 		max_retries: 3,
 		..Default::default()
 	});
 	ex.set_timeout(Duration::from_secs(60));
 	Mutex::new(ex)
-});
+}
+/// The `(client, instrument)` listings a venue is built from. For the merged view, order is dedupe
+/// priority: perps first (the deeper, more-watched market), then spot for spot-only listings.
+pub(super) fn listings(venue: Venue) -> Vec<(&'static Mutex<Box<dyn Exchange>>, Instrument)> {
+	match venue {
+		Venue::BinancePerp => vec![(&BINANCE, Instrument::Perp)],
+		Venue::BinanceSpot => vec![(&BINANCE, Instrument::Spot)],
+		Venue::BybitPerp => vec![(&BYBIT, Instrument::Perp)],
+		Venue::Merged => vec![(&BINANCE, Instrument::Perp), (&BYBIT, Instrument::Perp), (&BINANCE, Instrument::Spot)],
+	}
+}
+/// Per-pair `(open time, value)` bars, ascending.
+type Series = HashMap<Pair, Vec<(i64, f64)>>;
 /// Columnar {time, value}, kept as parallel arrays to stay small under gzip.
 #[derive(Deserialize, Serialize)]
-struct Line {
-	time: Vec<i64>, // UNIX seconds, ascending
-	value: Vec<f64>,
+pub(super) struct Line {
+	pub(super) time: Vec<i64>, // UNIX seconds, ascending
+	pub(super) value: Vec<f64>,
 }
+/// Normalized closes and quote volumes of every pair that fetched cleanly; failures are logged and left out.
 #[instrument(skip_all)]
-async fn collect_data(pairs: &[Pair], tf: Timeframe, range: RequestRange, instrument: Instrument, exchange: &dyn Exchange) -> Result<HashMap<Pair, Vec<(i64, f64)>>> {
-	tracing::info!("Starting data collection for {} pairs with tf={tf:?}, range={range:?}", pairs.len());
-
-	let progress = crate::dashboards::_core::Progress::of::<MarketStructureChart>();
-	progress.set_target(pairs.len());
+async fn collect_data(pairs: &[Pair], tf: Timeframe, bars: u32, instrument: Instrument, exchange: &dyn Exchange, progress: Progress) -> (Series, Series) {
+	tracing::info!("Starting data collection for {} {instrument} pairs with tf={tf:?}, bars={bars}", pairs.len());
 
 	//HACK: assumes we're never misaligned here
 	// ponytail: fixed cap keeps the per-pair burst under Binance's 2400 weight/min IP limit; tune if the universe grows
@@ -182,7 +314,7 @@ async fn collect_data(pairs: &[Pair], tf: Timeframe, range: RequestRange, instru
 	let results: Vec<_> = stream::iter(pairs.to_vec().into_iter().map(|pair| {
 		let symbol = Symbol::new(pair, instrument);
 		async move {
-			let r = match get_historical_data(symbol, tf, range, exchange).await {
+			let r = match get_historical_data(symbol, tf, bars, exchange).await {
 				Ok(series) => {
 					tracing::debug!("Successfully fetched {} data points for {symbol}", series.col_closes.len());
 					Ok((symbol, series))
@@ -199,9 +331,9 @@ async fn collect_data(pairs: &[Pair], tf: Timeframe, range: RequestRange, instru
 	.buffer_unordered(CONCURRENCY)
 	.collect()
 	.await;
-	let mut normalized: HashMap<Pair, Vec<(i64, f64)>> = HashMap::new();
+	let mut normalized = Series::new();
+	let mut volumes = Series::new();
 	let mut failed_pairs = Vec::new();
-	let mut btc_found = false;
 
 	results.into_iter().for_each(|result| match result {
 		Ok((symbol, series)) => {
@@ -215,9 +347,9 @@ async fn collect_data(pairs: &[Pair], tf: Timeframe, range: RequestRange, instru
 				Some(&(_, first)) if first > 0.0 => {
 					pts.iter_mut().for_each(|p| p.1 = (p.1 / first).ln());
 					if pts.iter().all(|p| p.1.is_finite()) {
-						if "BTC-USDT" == symbol.pair {
-							btc_found = true;
-						}
+						let mut vols: Vec<(i64, f64)> = series.col_open_times.iter().zip(&series.col_volumes).map(|(t, v)| (t.as_second(), *v)).collect();
+						vols.sort_by_key(|p| p.0);
+						volumes.insert(symbol.pair, vols);
 						normalized.insert(symbol.pair, pts);
 					} else {
 						tracing::warn!("dropping {symbol}: non-finite log-returns (bad close ≤ 0)");
@@ -234,66 +366,63 @@ async fn collect_data(pairs: &[Pair], tf: Timeframe, range: RequestRange, instru
 	let threshold = if success_rate < 70.0 { " - below 70% threshold" } else { "" };
 	tracing::info!("Fetched {} pairs, {} failed ({success_rate:.1}% success rate{threshold})", normalized.len(), failed_pairs.len());
 
-	if !btc_found {
-		tracing::error!(
-			"BTC-USDT missing from results. attempted: {}, succeeded: {}, failed: {}",
-			pairs.len(),
-			normalized.len(),
-			failed_pairs.len()
-		);
-		bail!("Failed to fetch data for BTC-USDT, aborting. Check logs for individual fetch errors.");
-	}
-
-	Ok(normalized)
+	(normalized, volumes)
 }
 #[allow(unused)]
-#[derive(Clone, Debug, Default, derive_new::new)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, derive_new::new)]
 //HACK: manual roll of a DataFrame. No checks for alignment (or proper vectorization, for that matter)...
-struct RelevantHistoricalData {
-	col_open_times: Vec<Timestamp>,
-	col_opens: Vec<f64>,
-	col_highs: Vec<f64>,
-	col_lows: Vec<f64>,
-	col_closes: Vec<f64>,
-	col_volumes: Vec<f64>,
+pub(super) struct RelevantHistoricalData {
+	pub(super) col_open_times: Vec<Timestamp>,
+	pub(super) col_opens: Vec<f64>,
+	pub(super) col_highs: Vec<f64>,
+	pub(super) col_lows: Vec<f64>,
+	pub(super) col_closes: Vec<f64>,
+	pub(super) col_volumes: Vec<f64>,
 }
 #[instrument(skip_all, fields(symbol = %symbol))]
-async fn get_historical_data(symbol: Symbol, tf: Timeframe, range: RequestRange, exchange: &dyn Exchange) -> Result<RelevantHistoricalData> {
-	tracing::debug!("Fetching klines for {symbol} (tf={tf:?}, range={range:?})");
+pub(super) async fn get_historical_data(symbol: Symbol, tf: Timeframe, bars: u32, exchange: &dyn Exchange) -> Result<RelevantHistoricalData> {
+	tracing::debug!("Fetching klines for {symbol} (tf={tf:?}, bars={bars})");
 
-	let klines = match exchange.klines(symbol, tf, range).await {
-		Ok(k) => {
-			tracing::debug!("Received {} klines for {symbol}", k.len());
-			k
-		}
-		Err(e) => {
-			tracing::error!("Failed to fetch klines for {symbol}: {e:?}");
-			return Err(e.into());
-		}
-	};
+	// spelled out of plain parts, so a recording's path doesn't hinge on how `v_exchanges` displays its types
+	let venue = format!("{:?}", exchange.name()).to_lowercase();
+	let key = format!("{venue}/klines/{}/{:?}/{}s/{bars}", symbol.pair, symbol.instrument, tf.duration().as_secs());
+	let range: RequestRange = bars.into();
+	cassette::tape(&key, async {
+		let klines = match exchange.klines(symbol, tf, range).await {
+			Ok(k) => {
+				tracing::debug!("Received {} klines for {symbol}", k.len());
+				k
+			}
+			Err(e) => {
+				tracing::error!("Failed to fetch klines for {symbol}: {e:?}");
+				return Err(e.into());
+			}
+		};
 
-	let mut open_time = Vec::new();
-	let mut open = Vec::new();
-	let mut high = Vec::new();
-	let mut low = Vec::new();
-	let mut close = Vec::new();
-	let mut volume = Vec::new();
-	for k in klines {
-		open_time.push(k.open_time);
-		open.push(k.open);
-		high.push(k.high);
-		low.push(k.low);
-		close.push(k.close);
-		volume.push(k.volume_quote);
-	}
-	Ok(RelevantHistoricalData {
-		col_open_times: open_time,
-		col_opens: open,
-		col_highs: high,
-		col_lows: low,
-		col_closes: close,
-		col_volumes: volume,
+		let mut open_time = Vec::new();
+		let mut open = Vec::new();
+		let mut high = Vec::new();
+		let mut low = Vec::new();
+		let mut close = Vec::new();
+		let mut volume = Vec::new();
+		for k in klines {
+			open_time.push(k.open_time);
+			open.push(k.open);
+			high.push(k.high);
+			low.push(k.low);
+			close.push(k.close);
+			volume.push(k.volume_quote);
+		}
+		Ok(RelevantHistoricalData {
+			col_open_times: open_time,
+			col_opens: open,
+			col_highs: high,
+			col_lows: low,
+			col_closes: close,
+			col_volumes: volume,
+		})
 	})
+	.await
 }
 #[derive(Deserialize, Serialize)]
 struct SeriesMeta {
@@ -305,23 +434,140 @@ struct SeriesMeta {
 struct LegendEntry {
 	label: String,
 	color: String,
+	/// Clicking the entry drills down into this pair.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pair: Option<String>,
+}
+impl MarketStructureChart {
+	/// Pairs drawn as interactive series — the ones a click can drill down into.
+	pub(super) fn highlighted(&self) -> impl Iterator<Item = &str> {
+		self.series.iter().map(|s| s.pair.as_str())
+	}
+}
+
+impl crate::dashboards::_core::SourceParams for ChartParams {
+	fn key(&self) -> String {
+		ChartParams::key(*self)
+	}
 }
 
 impl crate::dashboards::_core::SourceData for MarketStructureChart {
+	type Params = ChartParams;
+
 	fn decay_horizon() -> Timeframe {
 		"30m".into()
 	}
 
-	async fn fetch() -> Result<Self> {
-		let tf = "5m".into();
-		let range = (24 * 12 + 1).into(); // 24h, given `5m` tf
-		let mut exchange = BINANCE.lock().await;
-		market_structure_json(range, tf, &mut **exchange, Instrument::Perp).await
+	async fn fetch(params: &ChartParams) -> Result<Self> {
+		market_structure_json(*params).await
 	}
 
 	fn fmt_progress(loaded: usize, target: usize) -> String {
 		format!("pulled {loaded}/{target} pairs")
 	}
+
+	/// The requested span of bars for a fixed basket, each a seeded random walk, laid out by the real [`build_chart`].
+	fn fixture(params: &ChartParams) -> Option<Self> {
+		const BASES: [&str; 21] = [
+			"BTC", "ETH", "SOL", "XRP", "DOGE", "ADA", "AVAX", "LINK", "DOT", "LTC", "BCH", "NEAR", "APT", "ARB", "OP", "SUI", "1000PEPE", "TIA", "INJ", "SEI", "BTCDOM",
+		];
+		const END: i64 = 1_760_000_000; // fixed, so every run renders the same chart
+		let tf: Timeframe = params.tf.slug().into();
+		let step = tf.duration().as_secs() as i64;
+		let bars = params.bars() as i64;
+
+		let (mut closes, mut volumes, mut caps) = (Series::new(), Series::new(), HashMap::new());
+		for (i, base) in BASES.iter().enumerate() {
+			let pair: Pair = format!("{base}-USDT").parse().expect("fixture pairs are well-formed");
+			let drift = (i as f64 - BASES.len() as f64 / 2.0) * 2e-5;
+			// earlier in the basket trades more, like the real universe is led by BTC/ETH
+			let typical_volume = 1e6 * (BASES.len() - i) as f64;
+			let mut x = 0.0;
+			let (mut pts, mut vols) = (Vec::new(), Vec::new());
+			for bar in 0..bars {
+				if bar > 0 {
+					x += drift + 3e-3 * noise(i as u64, bar as u64);
+				}
+				let t = END - (bars - 1 - bar) * step;
+				pts.push((t, x));
+				vols.push((t, typical_volume * (1.0 + 0.5 * noise(i as u64 + BASES.len() as u64, bar as u64))));
+			}
+			closes.insert(pair, pts);
+			volumes.insert(pair, vols);
+			caps.insert(pair, 1e9 * ((BASES.len() - i) as f64).powi(2));
+		}
+		Some(build_chart(closes, &volumes, &caps, BASES.len(), tf, params.venue))
+	}
 }
 
-//TODO!!!: provide additional information: 1) BTCDOM, 2) average, 3) correlation, 4) volatility
+/// `(open time in UNIX seconds, close)` of the last `limit` bars of a Binance USDT perp (`BTCUSDT`), ascending. Straight
+/// off the REST endpoint rather than through an [`Exchange`] client, for the dashboards that only want a price line.
+pub async fn perp_klines(symbol: &str, interval: &str, limit: u32) -> Result<Vec<(i64, f64)>> {
+	let limit = limit.to_string();
+	let body = cassette::http(&format!("binance/klines/{symbol}/{interval}/{limit}"), async {
+		Ok(reqwest::Client::new()
+			.get("https://fapi.binance.com/fapi/v1/klines")
+			.query(&[("symbol", symbol), ("interval", interval), ("limit", limit.as_str())])
+			.send()
+			.await?
+			.error_for_status()?
+			.text()
+			.await?)
+	})
+	.await?;
+
+	// each kline is `[open_time, open, high, low, close, …]`, times in ms and prices as strings
+	let rows: Vec<Vec<serde_json::Value>> = serde_json::from_str(&body).wrap_err_with(|| format!("malformed klines for {symbol}"))?;
+	rows.iter()
+		.map(|r| {
+			let time = r.first().and_then(|t| t.as_i64()).ok_or_else(|| eyre!("kline for {symbol} without an open time"))?;
+			let close = r
+				.get(4)
+				.and_then(|c| c.as_str())
+				.and_then(|c| c.parse::<f64>().ok())
+				.ok_or_else(|| eyre!("kline for {symbol} without a close"))?;
+			Ok((time / 1000, close))
+		})
+		.collect()
+}
+
+/// Deterministic noise in `[-1, 1)` for fixture series: splitmix64 of `(series, bar)`.
+pub(super) fn noise(series: u64, bar: u64) -> f64 {
+	let mut z = ((series << 32) ^ bar).wrapping_add(0x9e3779b97f4a7c15);
+	z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+	z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+	z ^= z >> 31;
+	(z >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn collect_data_normalizes_to_log_returns() {
+		let pair = |s: &str| -> Pair { s.try_into().unwrap() };
+		let (btc, eth, xpl, unrecorded) = (pair("BTC-USDT"), pair("ETH-USDT"), pair("XPL-USDT"), pair("DOGE-USDT"));
+		let exchange = ExchangeName::Binance.init_client();
+		let progress = Progress::of::<MarketStructureChart>(&ChartParams::default());
+		let (closes, volumes) = cassette::replay(collect_data(&[btc, eth, xpl, unrecorded], "1h".into(), 4, Instrument::Perp, &*exchange, progress)).await;
+
+		// a zero first close can't anchor log-returns, and a failed fetch is just left out
+		let mut got: Vec<Pair> = closes.keys().copied().collect();
+		got.sort_by_key(|p| p.to_string());
+		assert_eq!(got, [btc, eth]);
+		assert_eq!(volumes.len(), 2);
+
+		let t0 = 1_760_000_400; // 2025-10-09 09:00 UTC
+		let expected = |first: f64, closes: [f64; 4]| -> Vec<(i64, f64)> { closes.iter().enumerate().map(|(i, c)| (t0 + 3600 * i as i64, (c / first).ln())).collect() };
+		assert_eq!(closes[&btc], expected(121_500.0, [121_500.0, 122_300.0, 121_800.0, 123_100.0]));
+		assert_eq!(closes[&eth], expected(4_380.5, [4_380.5, 4_412.25, 4_350.0, 4_455.75]));
+		assert_eq!(closes[&btc][0].1, 0.0);
+
+		// volumes stay raw quote volume, on the same bars
+		assert_eq!(
+			volumes[&btc],
+			[(t0, 912_345_678.5), (t0 + 3600, 1_034_567_890.25), (t0 + 7200, 876_543_210.0), (t0 + 10_800, 1_123_456_789.75)]
+		);
+	}
+}
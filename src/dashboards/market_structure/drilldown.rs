@@ -0,0 +1,301 @@
+//! Drill-down from a Market Structure series: one pair's OHLCV candles and its Binance global long/short
+//! account ratio, over the same venue/tf/lookback as the chart it was picked from. `lwc_draw.js`
+//! announces a click on a legend entry or highlighted series as an `ms:pair` window event; this panel
+//! listens for it, so the two never need to share reactive state across the deck.
+use leptos::{html::*, prelude::*};
+
+#[cfg(feature = "ssr")]
+use super::ChartParams;
+
+/// `ms:pair` event detail, JSON-encoded by `lwc_draw.js`.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[cfg_attr(not(feature = "hydrate"), allow(dead_code))] // only the client ever receives one
+struct Picked {
+	pair: String,
+	/// `/data/market_structure.json` query of the chart it was picked from.
+	query: String,
+}
+
+#[component]
+pub fn PairView() -> impl IntoView {
+	let picked = RwSignal::new(None::<Picked>);
+	let loading = RwSignal::new(false);
+	let banner = RwSignal::new(None::<String>);
+	#[cfg(feature = "hydrate")]
+	{
+		use send_wrapper::SendWrapper;
+		use wasm_bindgen::{JsCast, JsValue, closure::Closure};
+		use wasm_bindgen_futures::spawn_local;
+
+		let on_pick = Closure::<dyn Fn(JsValue)>::new(move |e: JsValue| {
+			let detail = js_sys::Reflect::get(&e, &"detail".into()).ok().and_then(|d| d.as_string()).unwrap_or_default();
+			match serde_json::from_str::<Picked>(&detail) {
+				Ok(p) => picked.set(Some(p)),
+				Err(err) => leptos::logging::error!("malformed ms:pair event `{detail}`: {err}"),
+			}
+		});
+		window().add_event_listener_with_callback("ms:pair", on_pick.as_ref().unchecked_ref()).unwrap();
+		let on_pick = SendWrapper::new(on_pick);
+		on_cleanup(move || {
+			let _ = window().remove_event_listener_with_callback("ms:pair", on_pick.as_ref().unchecked_ref());
+		});
+
+		Effect::new(move |_| {
+			let Some(requested) = picked.get() else { return };
+			let Some(el) = document().get_element_by_id("pair-chart") else { return };
+			let el: web_sys::HtmlElement = el.dyn_into().unwrap();
+			loading.set(true);
+			spawn_local(async move {
+				let url = format!("/data/klines/{}.json?{}", requested.pair, requested.query);
+				let resp = gloo_net::http::Request::get(&url).send().await;
+				// picked another pair while this was in flight — the newer request owns the chart
+				if picked.get_untracked().as_ref() != Some(&requested) {
+					return;
+				}
+				match resp {
+					Ok(resp) if !resp.ok() => {
+						let body = resp.text().await.unwrap_or_default();
+						banner.set(Some(format!("⚠ {} unavailable — {}", requested.pair, body.trim())));
+					}
+					Ok(resp) => match resp.text().await {
+						Ok(body) => {
+							let stale = serde_json::from_str::<StaleField>(&body).ok().and_then(|s| s.stale);
+							banner.set(v_utils::lwc::mount(el, "/lwc_pair.js", &body, "null").await.or(stale));
+						}
+						Err(e) => banner.set(Some(format!("⚠ {} unavailable — {e}", requested.pair))),
+					},
+					Err(e) => banner.set(Some(format!("⚠ {} unavailable — {e}", requested.pair))),
+				}
+				loading.set(false);
+			});
+		});
+	}
+
+	div().style("height:100%;display:flex;flex-direction:column").child((
+		move || {
+			banner.get().map(|msg| {
+				div()
+					.class("shrink-0 bg-amber-500/15 text-amber-200 text-[11px] leading-snug px-2 py-1 border-b border-amber-500/30")
+					.child(msg)
+			})
+		},
+		div()
+			.style("flex:1 1 auto;min-height:0;position:relative")
+			.child((div().id("pair-chart").style("height:100%"), move || match (picked.get(), loading.get()) {
+				(None, _) => Some(
+					div()
+						.class("absolute inset-0 flex items-center justify-center text-slate-400 text-xs")
+						.child("Click a pair in Market Structure's legend or chart")
+						.into_any(),
+				),
+				(Some(p), true) => Some(
+					div()
+						.class("absolute inset-0 flex items-center justify-center")
+						.child(super::super::LoadingIndicator(super::super::LoadingIndicatorProps { label: p.pair }))
+						.into_any(),
+				),
+				(Some(_), false) => None,
+			})),
+	))
+}
+
+#[cfg(feature = "hydrate")]
+#[derive(serde::Deserialize)]
+struct StaleField {
+	stale: Option<String>,
+}
+
+/// `/data/klines/{pair}.json`, with the same `?venue=&tf=&range=` as `/data/market_structure.json`. Only
+/// pairs that chart currently highlights are served: that's all the UI can pick, and it keeps the
+/// per-pair cache variants (and upstream calls) bounded.
+#[cfg(feature = "ssr")]
+pub async fn klines_json_handler(file: String, chart: ChartParams) -> axum::response::Response {
+	use axum::{http::StatusCode, response::IntoResponse};
+
+	let Some(pair) = file.strip_suffix(".json") else {
+		return StatusCode::NOT_FOUND.into_response();
+	};
+	if !chart.is_valid() {
+		return (
+			StatusCode::BAD_REQUEST,
+			format!("{} bars of {} over {} is out of range", chart.bars(), chart.tf.slug(), chart.range.slug()),
+		)
+			.into_response();
+	}
+	match super::highlighted(chart).await {
+		Ok(h) if h.iter().any(|p| p == pair) => {}
+		Ok(_) => return (StatusCode::NOT_FOUND, format!("{pair} is not highlighted on the {} chart", chart.key())).into_response(),
+		Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+	}
+
+	let params = data::CandleParams { pair: pair.to_owned(), chart };
+	match crate::dashboards::_core::load_with::<data::PairCandles>(&params).await {
+		Ok(loaded) => {
+			#[derive(serde::Serialize)]
+			struct Resp {
+				#[serde(flatten)]
+				candles: data::PairCandles,
+				#[serde(skip_serializing_if = "Option::is_none")]
+				stale: Option<String>,
+			}
+			let stale = loaded
+				.stale
+				.map(|s| format!("⚠ Exchange rate-limited — data from {} may be outdated. {}", s.fetched_at.strftime("%Y-%m-%d %H:%M UTC"), s.error));
+			axum::Json(Resp { candles: loaded.data, stale }).into_response()
+		}
+		Err(e) => {
+			tracing::error!("Failed to load klines for {}: {e:?}", crate::dashboards::_core::SourceParams::key(&params));
+			(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+		}
+	}
+}
+
+#[cfg(feature = "ssr")]
+mod data {
+	use color_eyre::eyre::{Result, eyre};
+	use serde::{Deserialize, Serialize};
+	use v_exchanges::prelude::*;
+	use v_utils::trades::{Pair, Timeframe};
+
+	use super::ChartParams;
+	use crate::dashboards::{
+		_core::{SourceData, SourceParams},
+		lsr,
+		market_structure::data::{Line, get_historical_data, listings, noise},
+	};
+
+	#[derive(Clone, Debug, Default)]
+	pub struct CandleParams {
+		/// As the chart's series name it, e.g. `BTC-USDT`.
+		pub pair: String,
+		pub chart: ChartParams,
+	}
+	impl SourceParams for CandleParams {
+		fn key(&self) -> String {
+			format!("{}-{}", self.pair, self.chart.key())
+		}
+	}
+
+	/// Columnar OHLCV, ascending, plus the long-account share over the same span when Binance has it.
+	#[derive(Deserialize, Serialize)]
+	pub struct PairCandles {
+		title: String,
+		time: Vec<i64>, // UNIX seconds
+		open: Vec<f64>,
+		high: Vec<f64>,
+		low: Vec<f64>,
+		close: Vec<f64>,
+		volume: Vec<f64>, // quote
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		lsr: Option<Line>, // share of accounts long, 0…1
+	}
+
+	impl SourceData for PairCandles {
+		type Params = CandleParams;
+
+		fn decay_horizon() -> Timeframe {
+			"5m".into()
+		}
+
+		/// Candles re-fetched on demand for whichever pair was clicked; their past is upstream's klines.
+		fn history_retention() -> Option<jiff::SignedDuration> {
+			None
+		}
+
+		/// Candles from the first of the venue's listings that has the pair — for the merged view that's
+		/// the same venue its line was charted from.
+		async fn fetch(params: &CandleParams) -> Result<Self> {
+			let pair: Pair = params.pair.parse().map_err(|e| eyre!("unparseable pair {}: {e:?}", params.pair))?;
+			let tf: Timeframe = params.chart.tf.slug().into();
+
+			let mut last_err = None;
+			let mut found = None;
+			for (client, instrument) in listings(params.chart.venue) {
+				let exchange = client.lock().await;
+				match get_historical_data(Symbol::new(pair, instrument), tf, params.chart.bars(), &**exchange).await {
+					Ok(h) => {
+						found = Some((h, format!("{} {instrument}", exchange.name())));
+						break;
+					}
+					Err(e) => last_err = Some(e),
+				}
+			}
+			let Some((h, venue)) = found else {
+				return Err(last_err.unwrap_or_else(|| eyre!("no listing for {}", params.pair)));
+			};
+
+			let mut bars: Vec<_> = (0..h.col_open_times.len())
+				.map(|i| (h.col_open_times[i].as_second(), h.col_opens[i], h.col_highs[i], h.col_lows[i], h.col_closes[i], h.col_volumes[i]))
+				.collect();
+			bars.sort_by_key(|b| b.0);
+
+			// positioning is context, not the point of the panel — a pair Binance has no ratio for just goes without
+			let lsr = match lsr::binance_ratio(lsr::Ratio::Global, pair, params.chart.tf.slug(), params.chart.bars()).await {
+				Ok(points) => Some(Line {
+					time: points.iter().map(|p| p.0).collect(),
+					value: points.iter().map(|p| p.1).collect(),
+				}),
+				Err(e) => {
+					tracing::debug!("no LSR for {}: {e}", params.pair);
+					None
+				}
+			};
+
+			// Binance keeps 30 days of ratios, less than the longest lookbacks span; say so rather than leave the pane to look cut off
+			let mut title = format!("{} · {venue} · {}", params.pair, params.chart.tf.slug());
+			if let (Some(first_bar), Some(first_lsr)) = (bars.first(), lsr.as_ref().and_then(|l| l.time.first())) {
+				if *first_lsr > first_bar.0 + tf.duration().as_secs() as i64 {
+					title.push_str(" · L/S ratio: last 30d only");
+				}
+			}
+
+			Ok(Self {
+				title,
+				time: bars.iter().map(|b| b.0).collect(),
+				open: bars.iter().map(|b| b.1).collect(),
+				high: bars.iter().map(|b| b.2).collect(),
+				low: bars.iter().map(|b| b.3).collect(),
+				close: bars.iter().map(|b| b.4).collect(),
+				volume: bars.iter().map(|b| b.5).collect(),
+				lsr,
+			})
+		}
+
+		/// A seeded random walk of candles, with a slowly drifting ratio under it.
+		fn fixture(params: &CandleParams) -> Option<Self> {
+			const END: i64 = 1_760_000_000;
+			let step = Timeframe::from(params.chart.tf.slug()).duration().as_secs() as i64;
+			let seed = params.pair.bytes().fold(0u64, |h, b| h.wrapping_mul(31).wrapping_add(b as u64));
+			let n = params.chart.bars() as u64;
+
+			let mut c = Self {
+				title: format!("{} · fixture · {}", params.pair, params.chart.tf.slug()),
+				time: Vec::new(),
+				open: Vec::new(),
+				high: Vec::new(),
+				low: Vec::new(),
+				close: Vec::new(),
+				volume: Vec::new(),
+				lsr: None,
+			};
+			let (mut lsr_t, mut lsr_v) = (Vec::new(), Vec::new());
+			let mut price = 100.0;
+			for bar in 0..n {
+				let open = price;
+				price *= (4e-3 * noise(seed, bar)).exp();
+				let wick = |k: u64| price.max(open) * 2e-3 * (1.0 + noise(seed ^ k, bar)).abs();
+				let t = END - (n - 1 - bar) as i64 * step;
+				c.time.push(t);
+				c.open.push(open);
+				c.high.push(price.max(open) + wick(1));
+				c.low.push(price.min(open) - wick(2));
+				c.close.push(price);
+				c.volume.push(1e6 * (1.5 + noise(seed ^ 3, bar)));
+				lsr_t.push(t);
+				lsr_v.push(0.55 + 0.08 * (bar as f64 / n as f64 * std::f64::consts::TAU).sin());
+			}
+			c.lsr = Some(Line { time: lsr_t, value: lsr_v });
+			Some(c)
+		}
+	}
+}
@@ -1,16 +1,30 @@
 #[cfg(feature = "ssr")]
+mod breadth;
+#[cfg(feature = "ssr")]
 mod data;
+mod drilldown;
+#[cfg(feature = "ssr")]
+mod overlays;
+mod params;
 
 use leptos::{html::*, prelude::*};
 
+#[cfg(feature = "ssr")]
+pub(super) use self::data::perp_klines;
+#[cfg(feature = "ssr")]
+pub use self::drilldown::klines_json_handler;
+pub use self::{
+	drilldown::PairView,
+	params::{ChartParams, ChartTf, Lookback, Venue},
+};
+
 #[component]
 pub fn MarketStructureView() -> impl IntoView {
+	let params = RwSignal::new(ChartParams::default());
 	let loading = RwSignal::new(true);
 	let stale = RwSignal::new(None::<String>);
 	#[cfg(feature = "hydrate")]
 	{
-		use gloo_timers::callback::Interval;
-		use send_wrapper::SendWrapper;
 		use wasm_bindgen::JsCast;
 		use wasm_bindgen_futures::spawn_local;
 
@@ -24,8 +38,14 @@ pub fn MarketStructureView() -> impl IntoView {
 		let mount_chart = move |on_done: Option<RwSignal<bool>>| {
 			if let Some(el) = document().get_element_by_id("ms-chart") {
 				let el: web_sys::HtmlElement = el.dyn_into().unwrap();
+				let requested = params.get_untracked();
 				spawn_local(async move {
-					match gloo_net::http::Request::get("/data/market_structure.json").send().await {
+					let resp = gloo_net::http::Request::get(&format!("/data/market_structure.json?{}", requested.query())).send().await;
+					// switched venue/tf/range while this was in flight — the newer request owns the chart
+					if params.get_untracked() != requested {
+						return;
+					}
+					match resp {
 						Ok(resp) if !resp.ok() => {
 							let body = resp.text().await.unwrap_or_default();
 							stale.set(Some(format!("⚠ Exchange unavailable — {}", body.trim())));
@@ -47,13 +67,46 @@ pub fn MarketStructureView() -> impl IntoView {
 				});
 			}
 		};
-		Effect::new(move |_| mount_chart(Some(loading)));
-		// shim reuses the chart + per-pair series, swapping data in place — no teardown, no loading overlay
-		let interval = SendWrapper::new(Interval::new(30 * 60 * 1000, move || mount_chart(None)));
-		on_cleanup(move || drop(interval));
+		Effect::new(move |_| {
+			params.track();
+			loading.set(true);
+			mount_chart(Some(loading));
+		});
+		// redraw as soon as the server persists a fresh copy of the chart on screen. shim reuses the
+		// chart + per-pair series, swapping data in place — no teardown, no loading overlay
+		super::on_source_event_where(
+			move |source| source == params.get_untracked().source_id(),
+			move |ev| {
+				if let super::SourceEvent::Refreshed { .. } = ev {
+					mount_chart(None);
+				}
+			},
+		);
 	}
 
+	let pickers = div().class("shrink-0 flex gap-1 px-2 py-1").child((
+		picker(
+			Venue::ALL.map(|v| (v, v.slug(), v.to_string())),
+			move || params.get().venue,
+			|_| true,
+			move |venue| params.update(|p| p.venue = venue),
+		),
+		picker(
+			ChartTf::ALL.map(|tf| (tf, tf.slug(), tf.slug().to_owned())),
+			move || params.get().tf,
+			|_| true,
+			move |tf| params.update(|p| *p = p.with_tf(tf)),
+		),
+		picker(
+			Lookback::ALL.map(|r| (r, r.slug(), r.slug().to_owned())),
+			move || params.get().range,
+			move |range| ChartParams { range, ..params.get() }.is_valid(),
+			move |range| params.update(|p| p.range = range),
+		),
+	));
+
 	div().style("height:100%;display:flex;flex-direction:column").child((
+		pickers,
 		move || {
 			stale.get().map(|msg| {
 				div()
@@ -69,18 +122,59 @@ pub fn MarketStructureView() -> impl IntoView {
 						.class("absolute inset-0 flex items-center justify-center")
 						.child(super::LoadingWithProgress(super::LoadingWithProgressProps {
 							label: "MarketStructure".into(),
-							name: "MarketStructureChart".into(),
+							name: params.get_untracked().source_id(),
 						}))
 				})
 			})),
 	))
 }
 
+/// A compact `<select>` over a closed set of `(value, slug, label)`; options `enabled` rejects are greyed out.
+fn picker<T: Copy + PartialEq + Send + Sync + 'static, const N: usize>(
+	options: [(T, &'static str, String); N],
+	selected: impl Fn() -> T + Copy + Send + Sync + 'static,
+	enabled: impl Fn(T) -> bool + Copy + Send + Sync + 'static,
+	pick: impl Fn(T) + 'static,
+) -> impl IntoView {
+	let values = options.each_ref().map(|(v, slug, _)| (*v, *slug));
+	select()
+		.class("bg-slate-900/80 text-slate-200 text-[11px] border border-slate-600 rounded px-1")
+		.on(leptos::ev::change, move |e| {
+			let slug = event_target_value(&e);
+			if let Some((v, _)) = values.iter().find(|(_, s)| *s == slug) {
+				pick(*v);
+			}
+		})
+		.child(
+			options
+				.into_iter()
+				.map(|(v, slug, label)| option().value(slug).selected(move || selected() == v).disabled(move || !enabled(v)).child(label))
+				.collect::<Vec<_>>(),
+		)
+}
+
+/// Only the default timeframe/lookback of each single venue is kept warm; other combinations fill on first view.
+/// [`Venue::Merged`] is left to fill on first view too: warming it would pull every venue's klines a second time each poll.
 #[cfg(feature = "ssr")]
-pub async fn market_structure_json_handler() -> axum::response::Response {
+pub(super) fn register(scheduler: &mut super::_core::Scheduler) {
+	// persisted under the bare name before the chart took a venue
+	super::_core::retire("MarketStructureChart");
+	for venue in Venue::ALL.into_iter().filter(|v| *v != Venue::Merged) {
+		scheduler.register_with::<data::MarketStructureChart>(ChartParams { venue, ..Default::default() });
+	}
+}
+
+/// `?venue=&tf=&range=`, each optional. Values outside the allowlists are rejected by the extractor,
+/// a timeframe/lookback pair that doesn't fit (see [`ChartParams::is_valid`]) by the handler.
+#[cfg(feature = "ssr")]
+pub async fn market_structure_json_handler(axum::extract::Query(params): axum::extract::Query<ChartParams>) -> axum::response::Response {
 	use axum::response::IntoResponse;
 
-	match crate::dashboards::_core::load::<data::MarketStructureChart>().await {
+	if !params.is_valid() {
+		let msg = format!("{} bars of {} over {} is out of range", params.bars(), params.tf.slug(), params.range.slug());
+		return (axum::http::StatusCode::BAD_REQUEST, msg).into_response();
+	}
+	match crate::dashboards::_core::load_with::<data::MarketStructureChart>(&params).await {
 		Ok(loaded) => {
 			#[derive(serde::Serialize)]
 			struct Resp {
@@ -88,15 +182,29 @@ pub async fn market_structure_json_handler() -> axum::response::Response {
 				chart: data::MarketStructureChart,
 				#[serde(skip_serializing_if = "Option::is_none")]
 				stale: Option<String>,
+				/// Echoed so a drill-down click can ask for the same venue/tf/range.
+				query: String,
 			}
 			let stale = loaded
 				.stale
 				.map(|s| format!("⚠ Exchange rate-limited — data from {} may be outdated. {}", s.fetched_at.strftime("%Y-%m-%d %H:%M UTC"), s.error));
-			axum::Json(Resp { chart: loaded.data, stale }).into_response()
+			axum::Json(Resp {
+				chart: loaded.data,
+				stale,
+				query: params.query(),
+			})
+			.into_response()
 		}
 		Err(e) => {
-			tracing::error!("Failed to build market structure: {e:?}");
+			tracing::error!("Failed to build market structure for {}: {e:?}", params.key());
 			(axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
 		}
 	}
 }
+
+/// Pairs the chart for `params` currently highlights, from its persisted copy (or a fresh build).
+#[cfg(feature = "ssr")]
+async fn highlighted(params: ChartParams) -> color_eyre::eyre::Result<Vec<String>> {
+	let chart = super::_core::load_with::<data::MarketStructureChart>(&params).await?.data;
+	Ok(chart.highlighted().map(str::to_owned).collect())
+}
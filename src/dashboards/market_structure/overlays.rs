@@ -0,0 +1,240 @@
+//! Whole-market series drawn over the per-pair lines: an equal-weighted average, quote-volume- and
+//! market-cap-weighted indices, BTC dominance, a rolling pair↔market correlation and a realized-volatility band around the
+//! average. All are computed on BTC's bar grid from the same normalized log-returns the chart already carries.
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use v_utils::trades::Pair;
+
+use super::data::Line;
+
+/// Binance's BTC dominance index, listed as a perp. Not a coin — charted as an overlay, never a pair.
+pub const BTCDOM: &str = "BTCDOM-USDT";
+
+#[derive(Deserialize, Serialize)]
+pub struct Overlay {
+	/// Stable across redraws, so the legend can remember what was toggled off.
+	id: String,
+	label: String,
+	color: String,
+	/// Own price scale, for values that aren't log-returns. `None` shares the main axis.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	scale: Option<String>,
+	line: Line,
+	/// Makes this a band: `line` is the upper edge, this the lower.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	lower: Option<Line>,
+	/// Initial state; the legend toggles it client-side.
+	visible: bool,
+}
+
+/// `grid` is BTC's bar times; `closes` excludes [`BTCDOM`], passed separately as `btcdom` when it fetched.
+/// `volumes` is keyed like `closes`; `caps` are current USD market caps, for whichever pairs have one.
+pub fn build(closes: &HashMap<Pair, Vec<(i64, f64)>>, volumes: &HashMap<Pair, Vec<(i64, f64)>>, caps: &HashMap<Pair, f64>, btcdom: Option<&[(i64, f64)]>, grid: &[i64]) -> Vec<Overlay> {
+	let n = grid.len();
+	if n < 2 {
+		return Vec::new();
+	}
+	let pairs: Vec<Pair> = closes.keys().copied().collect();
+	let aligned = align(&pairs, closes, grid);
+	let pair_rets = bar_returns(&aligned);
+
+	let average: Vec<f64> = (0..n).map(|i| mean(aligned.iter().filter_map(|row| row[i])).unwrap_or(0.0)).collect();
+	let index_ret: Vec<Option<f64>> = (0..n).map(|i| (i > 0).then(|| average[i] - average[i - 1])).collect();
+	let window = window(n);
+
+	let mut out = vec![Overlay {
+		id: "average".into(),
+		label: format!("avg of {}", closes.len()),
+		color: "#e2e8f0".into(),
+		scale: None,
+		line: line(grid, &average),
+		lower: None,
+		visible: true,
+	}];
+
+	// bar return of each pair weighted by its quote volume on that bar, cumulated from 0 like the pairs
+	let vols = align(&pairs, volumes, grid);
+	let mut vw = Vec::with_capacity(n);
+	let mut acc = 0.0;
+	for i in 0..n {
+		let (num, den) = pair_rets
+			.iter()
+			.zip(&vols)
+			.filter_map(|(r, v)| Some((r[i]?, v[i]?)))
+			.fold((0.0, 0.0), |(num, den), (r, v)| (num + r * v, den + v));
+		if den > 0.0 {
+			acc += num / den;
+		}
+		vw.push(acc);
+	}
+	out.push(Overlay {
+		id: "vw_index".into(),
+		label: "vol-weighted".into(),
+		color: "#34d399".into(),
+		scale: None,
+		line: line(grid, &vw),
+		lower: None,
+		visible: true,
+	});
+
+	// bar return of each pair weighted by its cap going into the bar. Caps are walked back from today's along the
+	// pair's own closes, taking supply as constant over the span: cap at bar j = cap now · e^(x_j − x_last)
+	let scaled: Vec<(usize, f64)> = pairs.iter().enumerate().filter_map(|(k, p)| Some((k, caps.get(p)? / closes[p].last()?.1.exp()))).collect();
+	if !scaled.is_empty() {
+		let mut cw = vec![0.0];
+		let mut acc = 0.0;
+		for i in 1..n {
+			let (num, den) = scaled
+				.iter()
+				.filter_map(|&(k, scale)| Some((pair_rets[k][i]?, scale * aligned[k][i - 1]?.exp())))
+				.fold((0.0, 0.0), |(num, den), (r, w)| (num + r * w, den + w));
+			if den > 0.0 {
+				acc += num / den;
+			}
+			cw.push(acc);
+		}
+		out.push(Overlay {
+			id: "cap_index".into(),
+			label: format!("cap-weighted, {} pairs", scaled.len()),
+			color: "#f472b6".into(),
+			scale: None,
+			line: line(grid, &cw),
+			lower: None,
+			visible: true,
+		});
+	}
+
+	if let Some(dom) = btcdom {
+		out.push(Overlay {
+			id: "btcdom".into(),
+			label: "BTCDOM".into(),
+			color: "#f97316".into(),
+			scale: None,
+			line: Line {
+				time: dom.iter().map(|p| p.0).collect(),
+				value: dom.iter().map(|p| round(p.1)).collect(),
+			},
+			lower: None,
+			visible: true,
+		});
+	}
+
+	// mean over pairs of corr(pair bar returns, index bar returns) across the trailing window
+	let corr: Vec<(i64, f64)> = (window..n)
+		.filter_map(|i| {
+			let lo = i + 1 - window;
+			let per_pair = pair_rets.iter().filter_map(|r| pearson((lo..=i).filter_map(|j| Some((r[j]?, index_ret[j]?))), window / 2));
+			Some((grid[i], mean(per_pair)?))
+		})
+		.collect();
+	if !corr.is_empty() {
+		let (time, value): (Vec<i64>, Vec<f64>) = corr.into_iter().unzip();
+		out.push(Overlay {
+			id: "correlation".into(),
+			label: format!("corr to avg, {window} bars"),
+			color: "#a78bfa".into(),
+			scale: Some("corr".into()),
+			line: line(&time, &value),
+			lower: None,
+			visible: false,
+		});
+	}
+
+	// avg ± the index's 1σ move over a window, from its trailing realized vol
+	let mut time = Vec::new();
+	let (mut upper, mut lower) = (Vec::new(), Vec::new());
+	for i in window..n {
+		let rets: Vec<f64> = (i + 1 - window..=i).filter_map(|j| index_ret[j]).collect();
+		let Some(sd) = stdev(&rets) else { continue };
+		let half = sd * (window as f64).sqrt();
+		time.push(grid[i]);
+		upper.push(average[i] + half);
+		lower.push(average[i] - half);
+	}
+	if !time.is_empty() {
+		out.push(Overlay {
+			id: "volatility".into(),
+			label: format!("avg ±1σ, {window} bars"),
+			color: "#38bdf8".into(),
+			scale: None,
+			line: line(&time, &upper),
+			lower: Some(line(&time, &lower)),
+			visible: false,
+		});
+	}
+
+	out
+}
+
+/// Each of `pairs` re-laid on `grid`, in that order; bars a pair doesn't have (not yet listed, gaps) stay `None`.
+pub(super) fn align(pairs: &[Pair], series: &HashMap<Pair, Vec<(i64, f64)>>, grid: &[i64]) -> Vec<Vec<Option<f64>>> {
+	let slot: HashMap<i64, usize> = grid.iter().enumerate().map(|(i, t)| (*t, i)).collect();
+	pairs
+		.iter()
+		.map(|p| {
+			let mut row = vec![None; grid.len()];
+			for &(t, v) in series.get(p).map(Vec::as_slice).unwrap_or_default() {
+				if let Some(&i) = slot.get(&t) {
+					row[i] = Some(v);
+				}
+			}
+			row
+		})
+		.collect()
+}
+
+/// Log-return of each bar over the previous one; `None` where either side is missing.
+pub(super) fn bar_returns(aligned: &[Vec<Option<f64>>]) -> Vec<Vec<Option<f64>>> {
+	aligned
+		.iter()
+		.map(|row| (0..row.len()).map(|i| if i == 0 { None } else { Some(row[i]? - row[i - 1]?) }).collect())
+		.collect()
+}
+
+/// Rolling window: a twelfth of the span, so 24h of `5m` looks back 2h and 30d of `1h` 2.5d.
+pub(super) fn window(bars: usize) -> usize {
+	(bars / 12).max(6).min(bars - 1)
+}
+
+pub(super) fn line(time: &[i64], value: &[f64]) -> Line {
+	Line {
+		time: time.to_vec(),
+		value: value.iter().map(|v| round(*v)).collect(),
+	}
+}
+
+fn round(v: f64) -> f64 {
+	(v * 1e5).round() / 1e5
+}
+
+fn mean(xs: impl Iterator<Item = f64>) -> Option<f64> {
+	let (sum, count) = xs.fold((0.0, 0usize), |(s, c), x| (s + x, c + 1));
+	(count > 0).then(|| sum / count as f64)
+}
+
+fn stdev(xs: &[f64]) -> Option<f64> {
+	if xs.len() < 2 {
+		return None;
+	}
+	let m = mean(xs.iter().copied())?;
+	Some((xs.iter().map(|x| (x - m).powi(2)).sum::<f64>() / (xs.len() - 1) as f64).sqrt())
+}
+
+/// `None` under `min_points` overlapping bars, or when either side is flat.
+fn pearson(pts: impl Iterator<Item = (f64, f64)>, min_points: usize) -> Option<f64> {
+	let pts: Vec<(f64, f64)> = pts.collect();
+	if pts.len() < min_points.max(2) {
+		return None;
+	}
+	let mx = mean(pts.iter().map(|p| p.0))?;
+	let my = mean(pts.iter().map(|p| p.1))?;
+	let (mut sxy, mut sxx, mut syy) = (0.0, 0.0, 0.0);
+	for (x, y) in &pts {
+		sxy += (x - mx) * (y - my);
+		sxx += (x - mx).powi(2);
+		syy += (y - my).powi(2);
+	}
+	let r = sxy / (sxx * syy).sqrt();
+	r.is_finite().then_some(r)
+}
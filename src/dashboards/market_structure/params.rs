@@ -0,0 +1,169 @@
+//! What a Market Structure chart covers: venue, bar timeframe and lookback. Each combination is its own
+//! cached source variant, so the choices are closed sets — an arbitrary `tf`/`range` would mint an
+//! unbounded number of cache entries and upstream fan-outs.
+
+/// Which listings the chart covers.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Venue {
+	#[default]
+	BinancePerp,
+	BinanceSpot,
+	BybitPerp,
+	/// All of the above, a pair listed on several of them charted once.
+	Merged,
+}
+impl Venue {
+	pub const ALL: [Self; 4] = [Self::BinancePerp, Self::BinanceSpot, Self::BybitPerp, Self::Merged];
+
+	/// `?venue=` value.
+	pub fn slug(self) -> &'static str {
+		match self {
+			Self::BinancePerp => "binance-perp",
+			Self::BinanceSpot => "binance-spot",
+			Self::BybitPerp => "bybit-perp",
+			Self::Merged => "merged",
+		}
+	}
+}
+impl std::fmt::Display for Venue {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(match self {
+			Self::BinancePerp => "Binance Perp",
+			Self::BinanceSpot => "Binance Spot",
+			Self::BybitPerp => "Bybit Perp",
+			Self::Merged => "Binance + Bybit",
+		})
+	}
+}
+
+/// Bar timeframe.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum ChartTf {
+	#[default]
+	#[serde(rename = "5m")]
+	M5,
+	#[serde(rename = "15m")]
+	M15,
+	#[serde(rename = "1h")]
+	H1,
+	#[serde(rename = "4h")]
+	H4,
+	#[serde(rename = "1d")]
+	D1,
+}
+impl ChartTf {
+	pub const ALL: [Self; 5] = [Self::M5, Self::M15, Self::H1, Self::H4, Self::D1];
+
+	/// `?tf=` value; also parses as a `v_utils` `Timeframe`.
+	pub fn slug(self) -> &'static str {
+		match self {
+			Self::M5 => "5m",
+			Self::M15 => "15m",
+			Self::H1 => "1h",
+			Self::H4 => "4h",
+			Self::D1 => "1d",
+		}
+	}
+
+	fn minutes(self) -> u32 {
+		match self {
+			Self::M5 => 5,
+			Self::M15 => 15,
+			Self::H1 => 60,
+			Self::H4 => 4 * 60,
+			Self::D1 => 24 * 60,
+		}
+	}
+}
+
+/// How far back the chart reaches.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Lookback {
+	#[default]
+	#[serde(rename = "24h")]
+	H24,
+	#[serde(rename = "3d")]
+	D3,
+	#[serde(rename = "7d")]
+	D7,
+	#[serde(rename = "30d")]
+	D30,
+}
+impl Lookback {
+	pub const ALL: [Self; 4] = [Self::H24, Self::D3, Self::D7, Self::D30];
+
+	/// `?range=` value.
+	pub fn slug(self) -> &'static str {
+		match self {
+			Self::H24 => "24h",
+			Self::D3 => "3d",
+			Self::D7 => "7d",
+			Self::D30 => "30d",
+		}
+	}
+
+	fn minutes(self) -> u32 {
+		match self {
+			Self::H24 => 24 * 60,
+			Self::D3 => 3 * 24 * 60,
+			Self::D7 => 7 * 24 * 60,
+			Self::D30 => 30 * 24 * 60,
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ChartParams {
+	#[serde(default)]
+	pub venue: Venue,
+	#[serde(default)]
+	pub tf: ChartTf,
+	#[serde(default)]
+	pub range: Lookback,
+}
+impl ChartParams {
+	/// Klines per pair are one request each, so a lookback needing more bars than an exchange serves
+	/// per request is out (Bybit caps at 1000).
+	const MAX_BARS: u32 = 1000;
+	/// Fewer points than this don't make a line worth reading.
+	const MIN_BARS: u32 = 7;
+
+	/// Bars per pair, counting the one the lookback starts in.
+	pub fn bars(self) -> u32 {
+		self.range.minutes() / self.tf.minutes() + 1
+	}
+
+	pub fn is_valid(self) -> bool {
+		(Self::MIN_BARS..=Self::MAX_BARS).contains(&self.bars())
+	}
+
+	/// `self` with `tf` swapped in, moving the lookback to the nearest one that still fits.
+	pub fn with_tf(self, tf: ChartTf) -> Self {
+		let with = |range| Self { tf, range, ..self };
+		if with(self.range).is_valid() {
+			return with(self.range);
+		}
+		let idx = |r: Lookback| Lookback::ALL.iter().position(|x| *x == r).unwrap() as i32;
+		Lookback::ALL
+			.into_iter()
+			.filter(|r| with(*r).is_valid())
+			.min_by_key(|r| (idx(*r) - idx(self.range)).abs())
+			.map_or(self, with)
+	}
+
+	/// Source variant key.
+	pub fn key(self) -> String {
+		format!("{}-{}-{}", self.venue.slug(), self.tf.slug(), self.range.slug())
+	}
+
+	/// `/data/market_structure.json` query string.
+	pub fn query(self) -> String {
+		format!("venue={}&tf={}&range={}", self.venue.slug(), self.tf.slug(), self.range.slug())
+	}
+
+	/// Name of the source variant, as it appears on pushed [`SourceEvent`](crate::dashboards::SourceEvent)s.
+	pub fn source_id(self) -> String {
+		format!("MarketStructureChart-{}", self.key())
+	}
+}
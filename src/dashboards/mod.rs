@@ -2,7 +2,10 @@
 pub mod _core;
 pub mod cme;
 pub mod deck;
+pub mod depth;
 pub mod fng;
+pub mod funding;
+pub mod liquidations;
 pub mod lsr;
 pub mod market_structure;
 pub mod vol;
@@ -38,26 +41,16 @@ pub fn LoadingIndicator(label: String) -> impl IntoView {
 	pre().child((format!("Loading {label}"), span().class("loading-dots").child("...")))
 }
 
-/// Loading indicator that polls [`dashboard_progress`] (~500ms) and shows `X/Y` while a fetch runs.
+/// Loading indicator that shows `X/Y` from pushed progress ticks while a fetch runs.
 #[component]
 pub fn LoadingWithProgress(label: String, name: String) -> impl IntoView {
 	let progress = RwSignal::new(None::<String>);
 	#[cfg(feature = "hydrate")]
-	{
-		use gloo_timers::callback::Interval;
-		use send_wrapper::SendWrapper;
-		use wasm_bindgen_futures::spawn_local;
-
-		let interval = SendWrapper::new(Interval::new(500, move || {
-			let name = name.clone();
-			spawn_local(async move {
-				if let Ok(p) = dashboard_progress(name).await {
-					progress.set(p);
-				}
-			});
-		}));
-		on_cleanup(move || drop(interval));
-	}
+	on_source_event(name, move |ev| {
+		if let SourceEvent::Progress { progress: p, .. } = ev {
+			progress.set(Some(p));
+		}
+	});
 	#[cfg(not(feature = "hydrate"))]
 	drop(name);
 	pre().child((
@@ -68,10 +61,99 @@ pub fn LoadingWithProgress(label: String, name: String) -> impl IntoView {
 		span().class("loading-dots").child("..."),
 	))
 }
-/// Reads live fetch progress registered under `name`.
-#[server]
-async fn dashboard_progress(name: String) -> Result<Option<String>, ServerFnError> {
-	Ok(_core::progress_of(&name))
+
+/// What `/data/events` pushes: a source got a fresh persisted copy, or its in-flight fetch moved.
+/// `source` is the id of the source concerned: its [`SourceData::name`](_core::SourceData::name), suffixed
+/// `-<key>` for a keyed variant (e.g. `MarketStructureChart-bybit-perp`).
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SourceEvent {
+	Refreshed { source: String, fetched_at: jiff::Timestamp },
+	Progress { source: String, progress: String },
+}
+impl SourceEvent {
+	pub fn source(&self) -> &str {
+		match self {
+			Self::Refreshed { source, .. } | Self::Progress { source, .. } => source,
+		}
+	}
+}
+
+/// Server-sent stream of every [`SourceEvent`], as JSON in unnamed `message` events.
+#[cfg(feature = "ssr")]
+pub async fn events_handler() -> axum::response::sse::Sse<impl futures::Stream<Item = Result<axum::response::sse::Event, std::convert::Infallible>>> {
+	use axum::response::sse::{Event, KeepAlive, Sse};
+	use tokio::sync::broadcast::error::RecvError;
+
+	let stream = futures::stream::unfold(_core::subscribe(), |mut rx| async move {
+		loop {
+			match rx.recv().await {
+				Ok(ev) => return Some((Ok(Event::default().json_data(&ev).expect("SourceEvent serializes")), rx)),
+				// a slow client only misses ticks; the next `Refreshed` resyncs it
+				Err(RecvError::Lagged(n)) => tracing::debug!("event stream lagged, skipped {n}"),
+				Err(RecvError::Closed) => return None,
+			}
+		}
+	});
+	Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Runs `handler` on every pushed event about `source` for as long as the calling component lives.
+#[cfg(feature = "hydrate")]
+pub(crate) fn on_source_event(source: impl Into<String>, handler: impl Fn(SourceEvent) + 'static) {
+	let source = source.into();
+	on_source_event_where(move |s| s == source, handler);
+}
+
+/// [`on_source_event`] for a panel whose source id changes as it's used (e.g. a selectable variant).
+/// All panels share one `EventSource`: a connection each would exhaust the browser's per-host
+/// HTTP/1.1 limit. The browser reconnects it on its own after a dropped connection.
+#[cfg(feature = "hydrate")]
+pub(crate) fn on_source_event_where(source: impl Fn(&str) -> bool + 'static, handler: impl Fn(SourceEvent) + 'static) {
+	use send_wrapper::SendWrapper;
+	use wasm_bindgen::{JsCast, closure::Closure};
+
+	thread_local! {
+		static EVENTS: web_sys::EventSource = web_sys::EventSource::new("/data/events").expect("EventSource is supported");
+	}
+
+	let closure = Closure::<dyn Fn(web_sys::MessageEvent)>::new(move |e: web_sys::MessageEvent| {
+		let Some(data) = e.data().as_string() else { return };
+		match serde_json::from_str::<SourceEvent>(&data) {
+			Ok(ev) if source(ev.source()) => handler(ev),
+			Ok(_) => {}
+			Err(err) => leptos::logging::error!("malformed source event `{data}`: {err}"),
+		}
+	});
+	EVENTS.with(|es| es.add_event_listener_with_callback("message", closure.as_ref().unchecked_ref()).unwrap());
+
+	let closure = SendWrapper::new(closure);
+	on_cleanup(move || {
+		EVENTS.with(|es| {
+			let _ = es.remove_event_listener_with_callback("message", closure.as_ref().unchecked_ref());
+		});
+	});
+}
+
+/// Starts background refresh of every dashboard source, so pages are served from a warm cache.
+#[cfg(feature = "ssr")]
+pub fn spawn_scheduler(settings: crate::config::LiveSettings) {
+	let mut scheduler = _core::Scheduler::default();
+	market_structure::register(&mut scheduler);
+	lsr::register(&mut scheduler);
+	cme::register(&mut scheduler, &settings);
+	vol::register(&mut scheduler);
+	fng::register(&mut scheduler);
+	funding::register(&mut scheduler);
+	liquidations::register(&mut scheduler);
+	depth::register(&mut scheduler, &settings);
+	scheduler.spawn(settings);
+}
+
+/// Starts checking users' dashboard alert rules against each fresh copy of the sources they watch.
+#[cfg(feature = "ssr")]
+pub fn spawn_alerts(settings: crate::config::LiveSettings, db: crate::auth::Database) {
+	lsr::spawn_alerts(settings, db);
 }
 
 #[component]
@@ -1,8 +1,12 @@
-use std::time::Duration;
+use std::{collections::BTreeMap, time::Duration};
 
-use color_eyre::eyre::{Result, bail, eyre};
+use color_eyre::eyre::{Result, WrapErr, bail, eyre};
+use serde::{Deserialize, de::DeserializeOwned};
 use v_utils::NowThen;
 
+use super::{ExpiryIv, VolWindow};
+use crate::dashboards::_core::cassette;
+
 ///// Fetch VIX volatility index data
 //pub async fn vix(client: &Client, api_key: &str, comparison_limit: u64) -> Result<f64> {
 //    let quote_url = format!("https://api.twelvedata.com/quote?symbol=VIX:CBOE&apikey={}", api_key);
@@ -101,14 +105,18 @@ pub async fn bvol(duration: Duration) -> Result<NowThen> {
 		bail!("Duration must be greater than 10m");
 	}
 
-	let bm = v_exchanges::bitmex::Bitmex::default();
-	let bvols = bm.bvol(duration).await.map_err(|e| {
-		tracing::warn!("Failed to fetch BVOL data from BitMEX: {e:?}");
-		eyre!("Failed to fetch BVOL data: {e}")
-	})?;
+	let prices: Vec<f64> = cassette::tape(&format!("bitmex/bvol/{}s", duration.as_secs()), async {
+		let bm = v_exchanges::bitmex::Bitmex::default();
+		let bvols = bm.bvol(duration).await.map_err(|e| {
+			tracing::warn!("Failed to fetch BVOL data from BitMEX: {e:?}");
+			eyre!("Failed to fetch BVOL data: {e}")
+		})?;
+		Ok(bvols.iter().map(|b| b.price).collect())
+	})
+	.await?;
 
-	let (now, then) = match (bvols.last(), bvols.first()) {
-		(Some(last), Some(first)) => (last.price, first.price),
+	let (now, then) = match (prices.last(), prices.first()) {
+		(Some(&last), Some(&first)) => (last, first),
 		_ => {
 			tracing::warn!("BVOL data is empty or incomplete");
 			bail!("BVOL data is empty or incomplete");
@@ -129,8 +137,234 @@ pub async fn vix(duration: Duration) -> Result<NowThen> {
 	}
 
 	let hours_floored = (duration.as_secs() / 3600) as u8; //NB: relies on previous check that should (for other reasons) ensure that this is a valid u8
-	v_exchanges::yahoo::vix_change("1h".into(), hours_floored).await.map_err(|e| {
-		tracing::warn!("Failed to fetch VIX data from Yahoo Finance: {e:?}");
-		eyre!("Failed to fetch VIX data: {e}")
+	cassette::tape(&format!("yahoo/vix/{hours_floored}h"), async {
+		v_exchanges::yahoo::vix_change("1h".into(), hours_floored).await.map_err(|e| {
+			tracing::warn!("Failed to fetch VIX data from Yahoo Finance: {e:?}");
+			eyre!("Failed to fetch VIX data: {e}")
+		})
 	})
+	.await
+}
+
+/// Annualized realized volatility of a Binance USDT perp over `window`, in %: the standard deviation of
+/// log close-to-close returns, scaled by √(bars per year). Bars widen with the window, so each call
+/// stays well under the kline cap while still holding enough returns to mean something.
+pub async fn realized_vol(symbol: &str, window: VolWindow) -> Result<f64> {
+	let (interval, bars) = match window {
+		VolWindow::H1 => ("1m", 60),
+		VolWindow::H24 => ("5m", 288),
+		VolWindow::D7 => ("1h", 168),
+	};
+	let closes = closes(symbol, interval, bars + 1).await?;
+	let returns: Vec<f64> = closes.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+	if returns.len() < 2 {
+		bail!("too few {interval} closes for {symbol} to measure volatility");
+	}
+	let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+	let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+	let bars_per_year = 365.0 * 24.0 * 3600.0 / (window.secs() / bars as u64) as f64;
+	Ok(variance.sqrt() * bars_per_year.sqrt() * 100.0)
+}
+
+/// Closes of the last `limit` bars, ascending; the last one is still forming.
+async fn closes(symbol: &str, interval: &str, limit: u32) -> Result<Vec<f64>> {
+	Ok(crate::dashboards::market_structure::perp_klines(symbol, interval, limit)
+		.await?
+		.into_iter()
+		.map(|(_, close)| close)
+		.collect())
+}
+
+/// Deribit's REST root. `DERIBIT_BASE_URL` overrides it, so the fetchers can be pointed at a local stand-in
+/// server; env rather than `Settings` for the same reason as [`cassette`]'s switches.
+fn deribit_base() -> String {
+	std::env::var("DERIBIT_BASE_URL")
+		.ok()
+		.filter(|u| !u.is_empty())
+		.map_or_else(|| "https://www.deribit.com".to_owned(), |u| u.trim_end_matches('/').to_owned())
+}
+
+/// `result` of a call to one of Deribit's public JSON-RPC-over-GET methods.
+async fn deribit<T: DeserializeOwned>(key: &str, method: &str, query: &[(&str, &str)]) -> Result<T> {
+	#[derive(Deserialize)]
+	struct Response<T> {
+		result: T,
+	}
+	let body = cassette::http(&format!("deribit/{key}"), async {
+		Ok(reqwest::Client::new()
+			.get(format!("{}/api/v2/public/{method}", deribit_base()))
+			.query(query)
+			.send()
+			.await?
+			.error_for_status()?
+			.text()
+			.await?)
+	})
+	.await?;
+	let r: Response<T> = serde_json::from_str(&body).wrap_err_with(|| format!("malformed Deribit `{method}` response"))?;
+	Ok(r.result)
+}
+
+/// Deribit's DVOL for `currency`, now and `duration` ago, off hourly closes.
+pub async fn dvol(currency: &str, duration: Duration) -> Result<NowThen> {
+	#[derive(Deserialize)]
+	struct Candles {
+		/// `[ms, open, high, low, close]`
+		data: Vec<(i64, f64, f64, f64, f64)>,
+	}
+	let end = jiff::Timestamp::now().as_millisecond();
+	let target = end - duration.as_millis() as i64;
+	let (start, end_s) = ((target - 2 * 3_600_000).to_string(), end.to_string());
+	let mut candles: Candles = deribit(
+		&format!("dvol/{currency}/{}s", duration.as_secs()),
+		"get_volatility_index_data",
+		&[("currency", currency), ("start_timestamp", &start), ("end_timestamp", &end_s), ("resolution", "3600")],
+	)
+	.await?;
+	candles.data.sort_by_key(|c| c.0);
+
+	let now = candles.data.last().ok_or_else(|| eyre!("no DVOL candles for {currency}"))?.4;
+	let then = candles.data.iter().min_by_key(|c| (c.0 - target).abs()).expect("non-empty").4;
+	Ok(NowThen {
+		duration: Some(duration),
+		..NowThen::new(now, then)
+	})
+}
+
+/// At-the-money implied vol of every listed `currency` option expiry, nearest first. Each expiry's ATM strike is
+/// the one closest to that expiry's own underlying (its future), and its IV the mean of the call's and put's mark IV.
+pub async fn atm_term_structure(currency: &str) -> Result<Vec<ExpiryIv>> {
+	#[derive(Deserialize)]
+	struct Summary {
+		instrument_name: String,
+		mark_iv: Option<f64>,
+		underlying_price: Option<f64>,
+	}
+	let summaries: Vec<Summary> = deribit(
+		&format!("book_summary/{currency}/option"),
+		"get_book_summary_by_currency",
+		&[("currency", currency), ("kind", "option")],
+	)
+	.await?;
+
+	// (expiry, label) -> [(strike, mark_iv, underlying)]
+	let mut by_expiry: BTreeMap<(jiff::Timestamp, String), Vec<(f64, f64, f64)>> = BTreeMap::new();
+	for s in summaries {
+		// `BTC-27DEC24-100000-C`
+		let parts: Vec<&str> = s.instrument_name.split('-').collect();
+		let &[_, expiry, strike, _] = parts.as_slice() else { continue };
+		let (Some(iv), Some(underlying), Ok(strike)) = (s.mark_iv, s.underlying_price, strike.parse::<f64>()) else {
+			continue;
+		};
+		if iv <= 0.0 {
+			continue;
+		}
+		match expiry_timestamp(expiry) {
+			Ok(at) => by_expiry.entry((at, expiry.to_owned())).or_default().push((strike, iv, underlying)),
+			Err(e) => tracing::warn!("skipping {}: {e}", s.instrument_name),
+		}
+	}
+
+	let now = jiff::Timestamp::now();
+	let term: Vec<ExpiryIv> = by_expiry
+		.into_iter()
+		.filter(|((at, _), _)| *at > now)
+		.filter_map(|((at, label), quotes)| {
+			let &(_, _, underlying) = quotes.first()?;
+			let atm = quotes.iter().map(|q| q.0).min_by(|a, b| (a - underlying).abs().total_cmp(&(b - underlying).abs()))?;
+			let ivs: Vec<f64> = quotes.iter().filter(|q| q.0 == atm).map(|q| q.1).collect();
+			Some(ExpiryIv {
+				expiry: label,
+				days: (at.as_second() - now.as_second()) as f64 / 86_400.0,
+				atm_iv: ivs.iter().sum::<f64>() / ivs.len() as f64,
+			})
+		})
+		.collect();
+	if term.is_empty() {
+		bail!("no live {currency} options with a mark IV on Deribit");
+	}
+	Ok(term)
+}
+
+/// Deribit expiries are `DMMMYY` / `DDMMMYY` (`7MAR25`, `27DEC24`), settling at 08:00 UTC.
+fn expiry_timestamp(s: &str) -> Result<jiff::Timestamp> {
+	const MONTHS: [&str; 12] = ["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+	let split = s.find(|c: char| c.is_ascii_alphabetic()).ok_or_else(|| eyre!("expiry `{s}` has no month"))?;
+	let (day, rest) = s.split_at(split);
+	if rest.len() != 5 {
+		bail!("malformed expiry `{s}`");
+	}
+	let (month, year) = rest.split_at(3);
+	let month = MONTHS.iter().position(|m| *m == month).ok_or_else(|| eyre!("unknown month in expiry `{s}`"))? as i8 + 1;
+	let (day, year): (i8, i16) = (
+		day.parse().wrap_err_with(|| format!("bad day in expiry `{s}`"))?,
+		year.parse().wrap_err_with(|| format!("bad year in expiry `{s}`"))?,
+	);
+	Ok(jiff::civil::Date::new(2000 + year, month, day)?.at(8, 0, 0, 0).to_zoned(jiff::tz::TimeZone::UTC)?.timestamp())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn expiry_formats() {
+		assert_eq!(expiry_timestamp("7MAR25").unwrap(), "2025-03-07T08:00:00Z".parse::<jiff::Timestamp>().unwrap());
+		assert_eq!(expiry_timestamp("27DEC24").unwrap(), "2024-12-27T08:00:00Z".parse::<jiff::Timestamp>().unwrap());
+		assert!(expiry_timestamp("27DECEMBER24").is_err());
+		assert!(expiry_timestamp("27XYZ24").is_err());
+		assert!(expiry_timestamp("PERPETUAL").is_err());
+	}
+
+	/// Both Deribit calls against a local stand-in, so the whole path from the request through the parsing runs.
+	#[tokio::test]
+	async fn deribit_against_local_server() {
+		let now = jiff::Timestamp::now();
+		let hour = 3_600_000;
+		let ms = now.as_millisecond();
+		let candles = serde_json::json!({ "result": { "data": [
+			[ms - 26 * hour, 0.0, 0.0, 0.0, 49.0],
+			[ms - 24 * hour, 0.0, 0.0, 0.0, 51.0],
+			[ms - 12 * hour, 0.0, 0.0, 0.0, 48.0],
+			[ms - hour, 0.0, 0.0, 0.0, 47.5],
+		], "continuation": null } })
+		.to_string();
+
+		// one expiry in each label format, both next year, plus one long since settled
+		let yy = (now.to_zoned(jiff::tz::TimeZone::UTC).year() + 1) % 100;
+		let (near, far) = (format!("7MAR{yy}"), format!("27DEC{yy}"));
+		let summary = |name: String, mark_iv: Option<f64>, underlying: f64| serde_json::json!({ "instrument_name": name, "mark_iv": mark_iv, "underlying_price": underlying });
+		let summaries = serde_json::json!({ "result": [
+			summary(format!("BTC-{near}-100000-C"), Some(50.0), 99_000.0),
+			summary(format!("BTC-{near}-100000-P"), Some(52.0), 99_000.0),
+			summary(format!("BTC-{near}-110000-C"), Some(55.0), 99_000.0),
+			// nearest the underlying, but without a mark, so the next strike out is ATM
+			summary(format!("BTC-{far}-100000-C"), None, 101_000.0),
+			summary(format!("BTC-{far}-100000-P"), None, 101_000.0),
+			summary(format!("BTC-{far}-105000-C"), Some(60.0), 101_000.0),
+			summary(format!("BTC-{far}-105000-P"), Some(62.0), 101_000.0),
+			summary("BTC-27DEC24-100000-C".to_owned(), Some(40.0), 101_000.0),
+			summary("BTC-PERPETUAL".to_owned(), Some(45.0), 101_000.0),
+		] })
+		.to_string();
+
+		let app = axum::Router::new()
+			.route("/api/v2/public/get_volatility_index_data", axum::routing::get(move || async move { candles }))
+			.route("/api/v2/public/get_book_summary_by_currency", axum::routing::get(move || async move { summaries }));
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let addr = listener.local_addr().unwrap();
+		tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+		std::env::set_var("DERIBIT_BASE_URL", format!("http://{addr}/"));
+
+		let dvol = dvol("BTC", Duration::from_hours(24)).await.unwrap();
+		assert_eq!((dvol.now, dvol.then), (47.5, 51.0));
+
+		let term = atm_term_structure("BTC").await.unwrap();
+		let got: Vec<(&str, f64)> = term.iter().map(|e| (e.expiry.as_str(), e.atm_iv)).collect();
+		assert_eq!(got, [(near.as_str(), 51.0), (far.as_str(), 61.0)]);
+		for (e, label) in term.iter().zip([&near, &far]) {
+			let to_expiry = (expiry_timestamp(label).unwrap().as_second() - now.as_second()) as f64 / 86_400.0;
+			assert!((e.days - to_expiry).abs() < 0.01, "{}: {} days, expected {to_expiry}", e.expiry, e.days);
+		}
+	}
 }
@@ -2,14 +2,64 @@
 mod data;
 
 use leptos::{html::*, prelude::*};
+use serde::{Deserialize, Serialize};
 use v_utils::NowThen;
 
 use super::{LoadingIndicator, LoadingIndicatorProps};
 
+/// Assets realized volatility is measured for, as `(label, Binance USDT perp)`.
+#[cfg_attr(not(feature = "ssr"), allow(dead_code))]
+const REALIZED: [(&str, &str); 2] = [("BTC", "BTCUSDT"), ("ETH", "ETHUSDT")];
+
+/// Span every figure in the panel is measured over.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub enum VolWindow {
+	H1,
+	#[default]
+	H24,
+	D7,
+}
+impl VolWindow {
+	pub const ALL: [Self; 3] = [Self::H1, Self::H24, Self::D7];
+
+	pub fn label(self) -> &'static str {
+		match self {
+			Self::H1 => "1h",
+			Self::H24 => "24h",
+			Self::D7 => "7d",
+		}
+	}
+
+	pub fn secs(self) -> u64 {
+		match self {
+			Self::H1 => 3600,
+			Self::H24 => 24 * 3600,
+			Self::D7 => 7 * 24 * 3600,
+		}
+	}
+}
+
 #[component]
 pub fn VolView() -> impl IntoView {
 	let trigger = RwSignal::new(());
-	let vol_resource = Resource::new(move || trigger.get(), move |_| async move { try_pull().await });
+	let window = RwSignal::new(VolWindow::default());
+	let vol_resource = Resource::new(move || (window.get(), trigger.get()), move |(window, _)| async move { try_pull(window).await });
+	let options_trigger = RwSignal::new(());
+	let options_resource = Resource::new(move || options_trigger.get(), |_| async move { options_vol().await });
+
+	// Refetch the moment the server persists a fresh copy
+	#[cfg(feature = "hydrate")]
+	super::on_source_event("VolData", move |ev| {
+		if let super::SourceEvent::Refreshed { .. } = ev {
+			trigger.update(|_| ());
+		}
+	});
+	#[cfg(feature = "hydrate")]
+	super::on_source_event("OptionsVol", move |ev| {
+		if let super::SourceEvent::Refreshed { .. } = ev {
+			options_trigger.update(|_| ());
+		}
+	});
 
 	// Set up retry interval - retry every 1 minute on error
 	#[cfg(not(feature = "ssr"))]
@@ -24,47 +74,433 @@ pub fn VolView() -> impl IntoView {
 				);
 			}
 		});
+		Effect::new(move || {
+			if let Some(Err(_)) = options_resource.get() {
+				set_timeout(
+					move || {
+						options_trigger.update(|_| ());
+					},
+					std::time::Duration::from_secs(60),
+				);
+			}
+		});
 	}
 
-	div().class("panel-center").child(Suspense(SuspenseProps {
-		fallback: { || LoadingIndicator(LoadingIndicatorProps { label: "Vol".into() }) }.into(),
-		children: ToChildren::to_children(move || {
-			IntoRender::into_render(move || match vol_resource.get() {
-				Some(Ok(vol_data)) => (pre().child(vol_data.to_string()),).into_any(),
-				Some(Err(e)) => (pre().child(format!("Error loading Vol data: {e} (retrying...)")),).into_any(),
-				None => (LoadingIndicator(LoadingIndicatorProps { label: "Vol".into() }),).into_any(),
+	let switcher = div().class("flex justify-center gap-1 mb-2 text-sm").child(
+		VolWindow::ALL
+			.into_iter()
+			.map(|w| {
+				button()
+					.attr("type", "button")
+					.class(move || if window.get() == w { "px-2 border rounded font-bold" } else { "px-2 border rounded" })
+					.on(leptos::ev::click, move |_| window.set(w))
+					.child(w.label())
 			})
+			.collect::<Vec<_>>(),
+	);
+
+	div().class("panel-center").child(div().class("w-full max-h-full overflow-auto").child((
+		switcher,
+		Suspense(SuspenseProps {
+			fallback: { || LoadingIndicator(LoadingIndicatorProps { label: "Vol".into() }) }.into(),
+			children: ToChildren::to_children(move || {
+				IntoRender::into_render(move || match vol_resource.get() {
+					Some(Ok(panel)) => vol_table(&panel).into_any(),
+					Some(Err(e)) => (pre().child(format!("Error loading Vol data: {e} (retrying...)")),).into_any(),
+					None => (LoadingIndicator(LoadingIndicatorProps { label: "Vol".into() }),).into_any(),
+				})
+			}),
+		}),
+		Suspense(SuspenseProps {
+			fallback: { || LoadingIndicator(LoadingIndicatorProps { label: "Deribit IV".into() }) }.into(),
+			children: ToChildren::to_children(move || {
+				IntoRender::into_render(move || match options_resource.get() {
+					Some(Ok(options)) => options_view(&options).into_any(),
+					Some(Err(e)) => (pre().child(format!("Error loading Deribit IV: {e} (retrying...)")),).into_any(),
+					None => (LoadingIndicator(LoadingIndicatorProps { label: "Deribit IV".into() }),).into_any(),
+				})
+			}),
 		}),
-	}))
+	)))
 }
 
-#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize, derive_new::new)]
+/// One poll: the VIX (S&P implied) and BitMEX's BVOL (BTC realized over 24h, not implied) with their live 24h
+/// change, and our own realized vol for every window.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct VolData {
 	vix: NowThen,
 	bvol: NowThen,
+	#[serde(default)]
+	realized: Vec<Realized>,
+}
+/// Annualized, in %.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct Realized {
+	asset: String,
+	window: VolWindow,
+	vol: f64,
+}
+impl VolData {
+	fn realized(&self, asset: &str, window: VolWindow) -> Option<f64> {
+		self.realized.iter().find(|r| r.asset == asset && r.window == window).map(|r| r.vol)
+	}
+}
+
+/// What the panel shows for one [`VolWindow`].
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct VolPanel {
+	window: VolWindow,
+	rows: Vec<VolRow>,
+	/// Deribit's BTC DVOL minus BTC realized over 24h: what options price in beyond what's been moving. Whatever the
+	/// window, as DVOL is a level rather than a measure over one.
+	implied_minus_realized: Option<f64>,
+	/// The last week of polls.
+	history: Vec<VolSeries>,
+}
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct VolRow {
+	name: String,
+	level: f64,
+	/// Since one window ago; `None` until the history reaches back that far.
+	change: Option<f64>,
+}
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct VolSeries {
+	label: String,
+	color: String,
+	time: Vec<i64>, // UNIX seconds
+	value: Vec<f64>,
+}
+
+fn vol_table(p: &VolPanel) -> impl IntoView {
+	let rows = p
+		.rows
+		.iter()
+		.map(|r| {
+			let (change, color) = match r.change {
+				Some(c) if c > 0.0 => (format!("{c:+.1}"), "#dc2626"),
+				Some(c) if c < 0.0 => (format!("{c:+.1}"), "#16a34a"),
+				Some(c) => (format!("{c:+.1}"), "inherit"),
+				None => ("–".to_owned(), "inherit"),
+			};
+			tr().child((
+				td().class("px-2 text-left").child(r.name.clone()),
+				td().class("px-2 text-right").child(format!("{:.1}", r.level)),
+				td().class("px-2 text-right").style(format!("color: {color}")).child(change),
+			))
+		})
+		.collect::<Vec<_>>();
+
+	div().child((
+		table().class("mx-auto text-sm font-mono").child((
+			thead().child(tr().child((
+				th().class("px-2"),
+				th().class("px-2 text-right").child("level"),
+				th().class("px-2 text-right").child(format!("Δ {}", p.window.label())),
+			))),
+			tbody().child(rows),
+		)),
+		p.implied_minus_realized.map(|s| div().class("mt-1 text-sm").child(format!("BTC DVOL − realized (24h): {s:+.1}"))),
+		history_chart(&p.history),
+	))
+}
+
+/// Inline SVG of every series on a shared % axis.
+fn history_chart(series: &[VolSeries]) -> impl IntoView {
+	const W: f64 = 300.0;
+	const H: f64 = 80.0;
+	let points = || series.iter().flat_map(|s| s.time.iter().zip(&s.value));
+	if points().next().is_none() {
+		return div().class("mt-2 text-xs text-gray-500").child("No history yet").into_any();
+	}
+	let (t0, t1) = points().fold((i64::MAX, i64::MIN), |(lo, hi), (t, _)| (lo.min(*t), hi.max(*t)));
+	let (lo, hi) = points().fold((f64::MAX, f64::MIN), |(lo, hi), (_, v)| (lo.min(*v), hi.max(*v)));
+	let (lo, hi) = (lo - 1.0, hi + 1.0);
+	let x = |t: i64| if t1 > t0 { (t - t0) as f64 / (t1 - t0) as f64 * W } else { W };
+	let y = |v: f64| (hi - v) / (hi - lo) * H;
+
+	let lines = series
+		.iter()
+		.map(|s| {
+			let pts = s.time.iter().zip(&s.value).map(|(t, v)| format!("{:.1},{:.1}", x(*t), y(*v))).collect::<Vec<_>>().join(" ");
+			leptos::svg::polyline()
+				.attr("points", pts)
+				.attr("fill", "none")
+				.attr("stroke", s.color.clone())
+				.attr("stroke-width", "1.5")
+				.attr("vector-effect", "non-scaling-stroke")
+		})
+		.collect::<Vec<_>>();
+	let legend = series
+		.iter()
+		.map(|s| span().child((span().style(format!("color: {}", s.color)).child("■ "), s.label.clone())))
+		.collect::<Vec<_>>();
+
+	div()
+		.class("mt-2")
+		.child((
+			leptos::svg::svg()
+				.attr("viewBox", format!("0 0 {W} {H}"))
+				.attr("preserveAspectRatio", "none")
+				.class("w-full h-20")
+				.child(lines),
+			div()
+				.class("flex flex-wrap justify-center gap-x-3 text-xs")
+				.child((legend, span().class("text-gray-500").child(format!("%, {lo:.0}–{hi:.0}")))),
+		))
+		.into_any()
+}
+
+/// Crypto implied vol off Deribit options: the DVOL index and the at-the-money term structure.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct OptionsVol {
+	currencies: Vec<CurrencyIv>,
+}
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct CurrencyIv {
+	currency: String,
+	/// Over 24h.
+	dvol: NowThen,
+	/// Nearest expiry first.
+	term: Vec<ExpiryIv>,
 }
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct ExpiryIv {
+	/// As Deribit labels it, e.g. `27DEC24`.
+	expiry: String,
+	/// To expiry, as of the fetch.
+	days: f64,
+	/// Annualized, in %.
+	atm_iv: f64,
+}
+
+fn options_view(o: &OptionsVol) -> impl IntoView {
+	let currencies = o
+		.currencies
+		.iter()
+		.map(|c| {
+			let change = c.dvol.now - c.dvol.then;
+			let term = c
+				.term
+				.iter()
+				.map(|e| {
+					tr().child((
+						td().class("px-2 text-left").child(e.expiry.clone()),
+						td().class("px-2 text-right").child(format!("{:.0}d", e.days)),
+						td().class("px-2 text-right").child(format!("{:.1}", e.atm_iv)),
+					))
+				})
+				.collect::<Vec<_>>();
+			div().class("mt-3").child((
+				div().child((
+					span().class("font-bold").child(format!("{} DVOL {:.1} ", c.currency, c.dvol.now)),
+					span()
+						.style(format!(
+							"color: {}",
+							if change > 0.0 {
+								"#dc2626"
+							} else if change < 0.0 {
+								"#16a34a"
+							} else {
+								"inherit"
+							}
+						))
+						.child(format!("{change:+.1} 24h")),
+				)),
+				table().class("mx-auto text-sm font-mono").child((
+					thead().child(tr().child((
+						th().class("px-2 text-left").child("expiry"),
+						th().class("px-2 text-right").child("to exp"),
+						th().class("px-2 text-right").child("ATM IV"),
+					))),
+					tbody().child(term),
+				)),
+			))
+		})
+		.collect::<Vec<_>>();
+	div().child((currencies, div().class("text-xs text-gray-500").child("Deribit options")))
+}
+
 #[server]
-async fn try_pull() -> Result<VolData, ServerFnError> {
-	super::_core::load::<VolData>().await.map(|l| l.data).map_err(|e| {
+async fn options_vol() -> Result<OptionsVol, ServerFnError> {
+	super::_core::load::<OptionsVol>().await.map(|l| l.data).map_err(|e| {
+		tracing::error!("Failed to load Deribit implied volatility: {e:?}");
+		ServerFnError::new(format!("Failed to load Deribit implied volatility: {e}"))
+	})
+}
+
+#[server]
+async fn try_pull(window: VolWindow) -> Result<VolPanel, ServerFnError> {
+	let current = super::_core::load::<VolData>().await.map(|l| l.data).map_err(|e| {
 		tracing::error!("Failed to load volatility data: {e:?}");
 		ServerFnError::new(format!("Failed to load volatility data: {e}"))
-	})
+	})?;
+	let since = jiff::Timestamp::now() - jiff::SignedDuration::from_hours(7 * 24 + 1);
+	// the panel still has its levels without the history; it only loses the changes and the chart
+	let history = super::_core::load_history::<VolData>(since..).unwrap_or_else(|e| {
+		tracing::warn!("Failed to read volatility history: {e:?}");
+		Vec::new()
+	});
+	// only the implied-minus-realized spread needs it
+	let dvol = match super::_core::load::<OptionsVol>().await {
+		Ok(o) => o.data.currencies.into_iter().find(|c| c.currency == "BTC").map(|c| c.dvol.now),
+		Err(e) => {
+			tracing::warn!("Failed to load Deribit DVOL for the implied-minus-realized spread: {e:?}");
+			None
+		}
+	};
+	Ok(panel(current, dvol, &history, window))
+}
+
+/// Changes are against the poll nearest one window back, if there's one close enough; the upstream indices'
+/// 24h change comes from upstream directly, so it's there from the first poll. `dvol` is Deribit's BTC DVOL.
+#[cfg(feature = "ssr")]
+fn panel(current: VolData, dvol: Option<f64>, history: &[super::_core::Snapshot<VolData>], window: VolWindow) -> VolPanel {
+	let span = window.secs() as i64;
+	let target = jiff::Timestamp::now().as_second() - span;
+	let tolerance = (span / 10).max(1800);
+	let then = history
+		.iter()
+		.min_by_key(|s| (s.fetched_at.as_second() - target).abs())
+		.filter(|s| (s.fetched_at.as_second() - target).abs() <= tolerance)
+		.map(|s| &s.data);
+
+	let index = |name: &str, now: &NowThen, of: fn(&VolData) -> &NowThen| VolRow {
+		name: name.to_owned(),
+		level: now.now,
+		change: match window {
+			VolWindow::H24 => Some(now.now - now.then),
+			_ => then.map(|t| now.now - of(t).now),
+		},
+	};
+	let mut rows = vec![index("VIX", &current.vix, |d| &d.vix), index("BVOL (24h realized)", &current.bvol, |d| &d.bvol)];
+	rows.extend(REALIZED.iter().filter_map(|(asset, _)| {
+		let level = current.realized(asset, window)?;
+		Some(VolRow {
+			name: format!("{asset} realized"),
+			level,
+			change: then.and_then(|t| t.realized(asset, window)).map(|v| level - v),
+		})
+	}));
+
+	let series = |label: &str, color: &str, value: &dyn Fn(&VolData) -> Option<f64>| {
+		let (time, value) = history.iter().filter_map(|s| Some((s.fetched_at.as_second(), value(&s.data)?))).unzip();
+		VolSeries {
+			label: label.to_owned(),
+			color: color.to_owned(),
+			time,
+			value,
+		}
+	};
+	VolPanel {
+		window,
+		implied_minus_realized: dvol.zip(current.realized("BTC", VolWindow::H24)).map(|(iv, rv)| iv - rv),
+		rows,
+		history: vec![
+			series("VIX", "#64748b", &|d| Some(d.vix.now)),
+			series("BVOL", "#f97316", &|d| Some(d.bvol.now)),
+			series(&format!("BTC RV {}", window.label()), "#2563eb", &|d| d.realized("BTC", window)),
+			series(&format!("ETH RV {}", window.label()), "#7c3aed", &|d| d.realized("ETH", window)),
+		],
+	}
+}
+
+#[cfg(feature = "ssr")]
+pub(super) fn register(scheduler: &mut super::_core::Scheduler) {
+	scheduler.register::<VolData>();
+	scheduler.register::<OptionsVol>();
 }
 #[cfg(feature = "ssr")]
 impl super::_core::SourceData for VolData {
+	type Params = ();
+
 	fn decay_horizon() -> v_utils::trades::Timeframe {
 		"1h".into()
 	}
 
-	async fn fetch() -> color_eyre::eyre::Result<Self> {
+	/// The widest window's changes, and the chart, read a week back.
+	fn history_retention() -> Option<jiff::SignedDuration> {
+		Some(jiff::SignedDuration::from_hours(8 * 24))
+	}
+
+	async fn fetch(_: &()) -> color_eyre::eyre::Result<Self> {
 		let duration = std::time::Duration::from_hours(24);
-		let (vix, bvol) = tokio::try_join!(data::vix(duration), data::bvol(duration))?;
-		Ok(VolData::new(vix, bvol))
+		let measures = REALIZED.iter().flat_map(|(asset, symbol)| {
+			VolWindow::ALL.into_iter().map(move |window| async move {
+				Ok::<_, color_eyre::eyre::Report>(Realized {
+					asset: asset.to_string(),
+					window,
+					vol: data::realized_vol(symbol, window).await?,
+				})
+			})
+		});
+		let (vix, bvol, realized) = tokio::try_join!(data::vix(duration), data::bvol(duration), futures::future::try_join_all(measures))?;
+		Ok(VolData { vix, bvol, realized })
+	}
+
+	fn fixture(_: &()) -> Option<Self> {
+		let over_24h = |now, then| NowThen {
+			duration: Some(std::time::Duration::from_hours(24)),
+			..NowThen::new(now, then)
+		};
+		let realized = [("BTC", [38.5, 41.2, 44.0]), ("ETH", [52.1, 55.7, 58.3])]
+			.into_iter()
+			.flat_map(|(asset, vols)| {
+				VolWindow::ALL.into_iter().zip(vols).map(move |(window, vol)| Realized {
+					asset: asset.to_owned(),
+					window,
+					vol,
+				})
+			})
+			.collect();
+		Some(VolData {
+			vix: over_24h(17.8, 16.9),
+			bvol: over_24h(48.2, 51.0),
+			realized,
+		})
 	}
 }
-//TODO!!!!: NowThen it
-impl std::fmt::Display for VolData {
-	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		write!(f, "VIX: {}; BVOL: {}", self.vix, self.bvol)
+#[cfg(feature = "ssr")]
+impl super::_core::SourceData for OptionsVol {
+	type Params = ();
+
+	fn decay_horizon() -> v_utils::trades::Timeframe {
+		"1h".into()
+	}
+
+	async fn fetch(_: &()) -> color_eyre::eyre::Result<Self> {
+		let currencies = ["BTC", "ETH"].map(|currency| async move {
+			let (dvol, term) = tokio::try_join!(data::dvol(currency, std::time::Duration::from_hours(24)), data::atm_term_structure(currency))?;
+			Ok::<_, color_eyre::eyre::Report>(CurrencyIv {
+				currency: currency.to_owned(),
+				dvol,
+				term,
+			})
+		});
+		Ok(Self {
+			currencies: futures::future::try_join_all(currencies).await?,
+		})
+	}
+
+	/// Contango on both, ETH a few points over BTC.
+	fn fixture(_: &()) -> Option<Self> {
+		let currency = |currency: &str, dvol: (f64, f64), base: f64| CurrencyIv {
+			currency: currency.to_owned(),
+			dvol: NowThen {
+				duration: Some(std::time::Duration::from_hours(24)),
+				..NowThen::new(dvol.0, dvol.1)
+			},
+			term: [("1JAN30", 1.0), ("7JAN30", 7.0), ("31JAN30", 31.0), ("28MAR30", 87.0), ("27SEP30", 270.0)]
+				.into_iter()
+				.map(|(expiry, days)| ExpiryIv {
+					expiry: expiry.to_owned(),
+					days,
+					atm_iv: base + 2.5 * f64::ln(days),
+				})
+				.collect(),
+		};
+		Some(Self {
+			currencies: vec![currency("BTC", (47.6, 49.1), 41.0), currency("ETH", (63.2, 61.8), 52.0)],
+		})
 	}
 }
@@ -0,0 +1,112 @@
+//! Fuzzy matching for pickers and search
+//!
+//! fzf-style: every query character has to appear in the candidate, in order. Runs of consecutive
+//! characters, matches at word starts and a match at the very start score extra; skipped characters cost a little.
+
+const MATCH: f64 = 1.0;
+const CONSECUTIVE: f64 = 2.0;
+const WORD_START: f64 = 1.5;
+const PREFIX: f64 = 3.0;
+const EXACT: f64 = 10.0;
+const GAP: f64 = 0.2;
+const LEADING_GAP: f64 = 0.05;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Match {
+	/// Higher is better; gaps can drive a long, scattered alignment below zero.
+	pub score: f64,
+	/// Char (not byte) positions in the candidate, ascending.
+	pub indices: Vec<usize>,
+}
+
+/// Best-scoring alignment of `query` in `candidate`, case-insensitively. `None` if it isn't a subsequence.
+pub fn score(query: &str, candidate: &str) -> Option<Match> {
+	let q: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+	let raw: Vec<char> = candidate.chars().collect();
+	let c: Vec<char> = raw.iter().map(|ch| ch.to_lowercase().next().unwrap_or(*ch)).collect();
+	let (m, n) = (q.len(), c.len());
+	if m == 0 || m > n {
+		return None;
+	}
+
+	let bonus = |j: usize| {
+		let start = if j == 0 {
+			PREFIX
+		} else if !raw[j - 1].is_alphanumeric() || (raw[j - 1].is_lowercase() && raw[j].is_uppercase()) {
+			WORD_START
+		} else {
+			0.0
+		};
+		MATCH + start
+	};
+
+	// best[i][j]: top score with q[i] placed on c[j]; from[i][j]: where q[i - 1] sat
+	let mut best = vec![vec![f64::NEG_INFINITY; n]; m];
+	let mut from = vec![vec![usize::MAX; n]; m];
+	for j in 0..n {
+		if c[j] == q[0] {
+			best[0][j] = bonus(j) - LEADING_GAP * j as f64;
+		}
+	}
+	for i in 1..m {
+		// running max over k < j - 1 of best[i - 1][k] + GAP * (k + 1), so a gap of j - k - 1 costs GAP each
+		let mut run = (f64::NEG_INFINITY, usize::MAX);
+		for j in i..n {
+			if j >= 2 {
+				let k = j - 2;
+				let v = best[i - 1][k] + GAP * (k + 1) as f64;
+				if v > run.0 {
+					run = (v, k);
+				}
+			}
+			if c[j] != q[i] {
+				continue;
+			}
+			let adjacent = best[i - 1][j - 1] + CONSECUTIVE;
+			let gapped = run.0 - GAP * j as f64;
+			let (prev, k) = if adjacent >= gapped { (adjacent, j - 1) } else { (gapped, run.1) };
+			if prev.is_finite() {
+				best[i][j] = prev + bonus(j);
+				from[i][j] = k;
+			}
+		}
+	}
+
+	let (mut j, top) = best[m - 1].iter().copied().enumerate().max_by(|a, b| a.1.total_cmp(&b.1))?;
+	if !top.is_finite() {
+		return None;
+	}
+	let mut indices = vec![0; m];
+	for i in (0..m).rev() {
+		indices[i] = j;
+		j = from[i][j];
+	}
+	let exact = if m == n { EXACT } else { 0.0 };
+	Some(Match { score: top + exact, indices })
+}
+
+/// `text` cut into runs of `(chunk, is_matched)`, for rendering the matched characters differently.
+pub fn highlight(text: &str, indices: &[usize]) -> Vec<(String, bool)> {
+	let mut runs: Vec<(String, bool)> = Vec::new();
+	for (i, ch) in text.chars().enumerate() {
+		let hit = indices.contains(&i);
+		match runs.last_mut() {
+			Some((s, h)) if *h == hit => s.push(ch),
+			_ => runs.push((ch.to_string(), hit)),
+		}
+	}
+	runs
+}
+
+/// What a ticker is searched by: `1000PEPE-USDT` and `PEPEUSDT` both become `PEPE`. Returns the key and
+/// how many leading chars of `s` were dropped, to map match indices back onto `s`.
+pub fn symbol_key(s: &str) -> (String, usize) {
+	let upper = s.trim().to_uppercase();
+	let upper = upper.strip_suffix("-USDT").or_else(|| upper.strip_suffix("USDT")).filter(|b| !b.is_empty()).unwrap_or(&upper);
+	let base = match upper.strip_prefix("1000") {
+		Some(rest) => rest.trim_start_matches('0'),
+		None => upper,
+	};
+	let base = if base.is_empty() { upper } else { base };
+	(base.to_owned(), upper.chars().count() - base.chars().count())
+}
@@ -9,6 +9,7 @@ pub mod auth;
 pub mod blog;
 pub mod config;
 pub mod dashboards;
+pub mod fuzzy;
 pub mod keyboard;
 pub mod tmp;
 
@@ -39,6 +39,11 @@ async fn main() {
 
 	let db = Database::try_new().await.expect("failed to initialize database");
 
+	// keep every dashboard source warm in the background, so the first visitor after a source's
+	// refresh interval isn't the one waiting through its upstream fan-out
+	site::dashboards::spawn_scheduler(live_settings.clone());
+	site::dashboards::spawn_alerts(live_settings.clone(), db.clone());
+
 	// Warn about missing configurations
 	if !settings.google_oauth.is_configured() {
 		tracing::warn!("Google OAuth is not configured. Add [google_oauth] section with client_id and client_secret to enable Google sign-in.");
@@ -60,6 +65,8 @@ async fn main() {
 	let leptos_options_clone = leptos_options.clone();
 	let live_settings_clone = live_settings.clone();
 	let live_settings_route = live_settings.clone();
+	let live_settings_klines = live_settings.clone();
+	let live_settings_liquidations = live_settings.clone();
 	let db_clone = db.clone();
 
 	let app = Router::new()
@@ -79,17 +86,49 @@ async fn main() {
 		// context so the shared SourceData `load()` (mock check) resolves, same as server fns do.
 		.route(
 			"/data/market_structure.json",
-			axum::routing::get(move || {
+			axum::routing::get(move |query: axum::extract::Query<site::dashboards::market_structure::ChartParams>| {
 				let live_settings = live_settings_route.clone();
 				let owner = Owner::new();
 				owner.with(|| {
 					ScopedFuture::new(async move {
 						provide_context(live_settings);
-						site::dashboards::market_structure::market_structure_json_handler().await
+						site::dashboards::market_structure::market_structure_json_handler(query).await
+					})
+				})
+			}),
+		)
+		// per-pair OHLCV behind the Market Structure drill-down; same owner + context setup as above
+		.route(
+			"/data/klines/{file}",
+			axum::routing::get(
+				move |axum::extract::Path(file): axum::extract::Path<String>, query: axum::extract::Query<site::dashboards::market_structure::ChartParams>| {
+					let live_settings = live_settings_klines.clone();
+					let owner = Owner::new();
+					owner.with(|| {
+						ScopedFuture::new(async move {
+							provide_context(live_settings);
+							site::dashboards::market_structure::klines_json_handler(file, query.0).await
+						})
+					})
+				},
+			),
+		)
+		// liquidation heatmap for its Lightweight Charts panel; same owner + context setup as above
+		.route(
+			"/data/liquidations.json",
+			axum::routing::get(move || {
+				let live_settings = live_settings_liquidations.clone();
+				let owner = Owner::new();
+				owner.with(|| {
+					ScopedFuture::new(async move {
+						provide_context(live_settings);
+						site::dashboards::liquidations::liquidations_json_handler().await
 					})
 				})
 			}),
 		)
+		// server-push of source refreshes + fetch progress; panels subscribe instead of polling
+		.route("/data/events", axum::routing::get(site::dashboards::events_handler))
 		.fallback(file_and_error_handler(move |_| {
 			provide_context(live_settings.clone());
 			provide_context(db.clone());